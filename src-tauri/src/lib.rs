@@ -5,10 +5,10 @@ use std::sync::Arc;
 use parking_lot::RwLock;
 use tauri::{Manager, AppHandle, Emitter};
 use tokio::sync::mpsc;
-use modules::{PackInfo, PackType, Settings, FileMover, LogEntry, MoveOperation, scan_single_pack};
+use modules::{AttentionReason, calculate_folder_size, long_path, zip_pack_folder, classify_archive, compression_info, CompressionInfo, json_error, read_archive_json_files, JsonError, attention_message_for, PackInfo, PackType, Settings, FileMover, LogEntry, MoveHistory, MoveOperation, install_folder_name, verify_archive_entries, ArchiveHealth, encode_icon_data, extract_authors, extract_dependencies, extract_description, extract_format_version, extract_matching_files, extract_pack_to_destination, folder_name_issues, find_pack_readme, list_pack_files, sanitize_folder_name, has_zip_magic, parse_manifest_json, parse_manifest_json_lenient, read_folder_display_name, scan_single_pack, load_history_from_file, append_history_to_file, mark_reverted_in_history_file, read_receipt, pack_dirs_in, resolve_pack_dir, staged_dest_base, strip_pack_suffix, RECEIPT_FILE_NAME, STAGED_FILE_NAME, collect_relative_files, find_skins_json, validate_4d_folder, Validation4D, diff_pack_paths, PackDiff, cached_folder_icon, evict_stale_icons, remove_cached_icons, fingerprint_folder, fingerprint_similarity, read_pack_skins, SkinEntry, profile_pack_contents, ContentProfile, classify_drive, drive_root, DriveKind};
 use serde::{Deserialize, Serialize};
 use notify::{Watcher, RecursiveMode, Event, EventKind};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::AtomicBool;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
@@ -47,13 +47,24 @@ static EXTRACT_VERSION_4: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s(\d+(?:\.\d+
 static EXTRACT_VERSION_5: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s(\d+)\s*\(").unwrap());
 static EXTRACT_VERSION_6: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s(\d+(?:\.\d+)*)\s").unwrap());
 
+static PACK_MODULE_TYPE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#""type"(\s*:\s*)"(?:data|resources|skin_pack|world_template)""#).unwrap());
+
 struct AppState {
     settings: RwLock<Settings>,
-    watching: AtomicBool,
     debug_mode: AtomicBool,
-    watch_stop_tx: parking_lot::Mutex<Option<std::sync::mpsc::SyncSender<()>>>,
+    // Set by cancel_operation, checked by long folder copies
+    cancel_requested: AtomicBool,
+    // Running folder watchers, keyed by watcher name
+    watchers: parking_lot::Mutex<std::collections::HashMap<&'static str, RunningWatcher>>,
+    // Events the watchers have passed on to the frontend since launch
+    watcher_events: std::sync::atomic::AtomicU64,
+    fingerprint_cache: parking_lot::Mutex<std::collections::HashMap<String, CachedFingerprint>>,
+    history: MoveHistory,
 }
 
+type CachedFingerprint = (std::time::SystemTime, Arc<std::collections::HashSet<u64>>);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WatcherEvent {
     pub timestamp: String,
@@ -82,26 +93,49 @@ async fn scan_packs(directory: String, app: AppHandle) -> Result<Vec<PackInfo>,
     let _ = app.emit("progress", serde_json::json!({
         "current": 0,
         "total": 0,
-        "message": "Finding pack files..."
+        "message": "Finding pack files...",
+        "phase": "enumerating"
     }));
     
-    let pack_extensions = ["mcpack", "mcaddon", "mctemplate"];
-    let files: Vec<std::path::PathBuf> = std::fs::read_dir(path)
-        .map_err(|e| format!("Failed to read directory: {}", e))?
-        .filter_map(|e| e.ok())
-        .map(|e| e.path())
-        .filter(|p| {
-            p.extension()
-                .and_then(|e| e.to_str())
-                .map(|ext| pack_extensions.contains(&ext.to_lowercase().as_str()))
-                .unwrap_or(false)
+    let (scan_unknown_extensions, max_depth, skip_dirs, icon_names) = {
+        let state = app.state::<AppState>();
+        let settings = state.settings.read();
+        let max_depth = if settings.scan_recursive.unwrap_or(false) {
+            settings.scan_depth.unwrap_or(DEFAULT_SCAN_DEPTH)
+        } else {
+            0
+        };
+        // Never descend into the install folders if they live under the scan location
+        let skip_dirs: Vec<std::path::PathBuf> = [
+            settings.behavior_pack_path.as_ref(),
+            settings.resource_pack_path.as_ref(),
+            settings.skin_pack_path.as_ref(),
+            settings.world_template_path.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|p| {
+            let base = std::path::Path::new(p);
+            base.canonicalize().unwrap_or_else(|_| base.to_path_buf())
         })
         .collect();
+        (settings.scan_unknown_extensions.unwrap_or(false), max_depth, skip_dirs, settings.icon_names())
+    };
+
+    let files = collect_pack_files(path, max_depth, &skip_dirs, scan_unknown_extensions)?;
+    
+    tokio::task::spawn_blocking(evict_stale_icons);
     
     let total_files = files.len();
     
     if total_files == 0 {
         emit_log(&app, "INFO", "No pack files found");
+        let _ = app.emit("progress", serde_json::json!({
+            "current": 0,
+            "total": 0,
+            "message": "No pack files found",
+            "phase": "done"
+        }));
         return Ok(vec![]);
     }
     
@@ -110,7 +144,8 @@ async fn scan_packs(directory: String, app: AppHandle) -> Result<Vec<PackInfo>,
     let _ = app.emit("progress", serde_json::json!({
         "current": 0,
         "total": total_files,
-        "message": "Scanning packs in parallel..."
+        "message": "Scanning packs in parallel...",
+        "phase": "scanning"
     }));
     
     let app_for_progress = app.clone();
@@ -130,7 +165,7 @@ async fn scan_packs(directory: String, app: AppHandle) -> Result<Vec<PackInfo>,
             .par_iter()
             .flat_map(|file| {
                 let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    scan_single_pack(file)
+                    scan_single_pack(file, &icon_names)
                 }));
                 
                 let current = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
@@ -140,7 +175,8 @@ async fn scan_packs(directory: String, app: AppHandle) -> Result<Vec<PackInfo>,
                     let _ = app_clone.emit("progress", serde_json::json!({
                         "current": current,
                         "total": total_for_progress,
-                        "message": format!("Scanned {}/{}", current, total_for_progress)
+                        "message": format!("Scanned {}/{}", current, total_for_progress),
+                        "phase": "scanning"
                     }));
                 }
                 
@@ -157,6 +193,13 @@ async fn scan_packs(directory: String, app: AppHandle) -> Result<Vec<PackInfo>,
     
     emit_log(&app, "INFO", &format!("Found {} packs in {} files", packs.len(), total_files));
     
+    let _ = app.emit("progress", serde_json::json!({
+        "current": 0,
+        "total": total_files,
+        "message": "Reading pack sizes...",
+        "phase": "sizing"
+    }));
+    
     let mut size_cache: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
     for file in &files {
         if let Ok(metadata) = std::fs::metadata(file) {
@@ -172,158 +215,624 @@ async fn scan_packs(directory: String, app: AppHandle) -> Result<Vec<PackInfo>,
         }
     }
     
+    let _ = app.emit("progress", serde_json::json!({
+        "current": total_files,
+        "total": total_files,
+        "message": "Pack sizes read",
+        "phase": "sizing"
+    }));
+    
     {
         let state = app.state::<AppState>();
         let mut settings = state.settings.write();
         settings.scan_location = Some(directory);
         let _ = save_settings_to_file(&settings);
     }
+    apply_pack_tags(&app, &mut packs);
     
     let _ = app.emit("progress", serde_json::json!({
         "current": total_files,
         "total": total_files,
         "message": "Scan complete",
-        "estimated_seconds": 0
+        "estimated_seconds": 0,
+        "phase": "done"
     }));
     
     Ok(packs)
 }
 
+const DEFAULT_SCAN_DEPTH: u32 = 3;
+
+/// Collects pack files under `root`, descending at most `max_depth` levels of
+/// subfolders (0 = top level only) and skipping any folder in `skip_dirs`.
+fn collect_pack_files(
+    root: &std::path::Path,
+    max_depth: u32,
+    skip_dirs: &[std::path::PathBuf],
+    scan_unknown_extensions: bool,
+) -> Result<Vec<std::path::PathBuf>, String> {
+    let pack_extensions = ["mcpack", "mcaddon", "mctemplate"];
+    let mut files = Vec::new();
+    let mut stack = vec![(root.to_path_buf(), 0u32)];
+    
+    while let Some((dir, depth)) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if depth == 0 => return Err(format!("Failed to read directory: {}", e)),
+            Err(_) => continue,
+        };
+        
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.is_dir() {
+                if depth < max_depth {
+                    let canonical = p.canonicalize().unwrap_or_else(|_| p.clone());
+                    if !skip_dirs.iter().any(|s| canonical.starts_with(s)) {
+                        stack.push((p, depth + 1));
+                    }
+                }
+                continue;
+            }
+            
+            let known = p.extension()
+                .and_then(|e| e.to_str())
+                .map(|ext| pack_extensions.contains(&ext.to_lowercase().as_str()))
+                .unwrap_or(false);
+            // Renamed or mangled downloads still scan if they are really zip archives
+            if known || (scan_unknown_extensions && p.is_file() && has_zip_magic(&p)) {
+                files.push(p);
+            }
+        }
+    }
+    
+    Ok(files)
+}
+
+/// Scans one dropped file instead of its whole parent directory.
+#[tauri::command]
+async fn scan_file(path: String, app: AppHandle) -> Result<Vec<PackInfo>, String> {
+    let file_path = PathBuf::from(&path);
+    if !file_path.is_file() {
+        return Err(format!("File does not exist: {}", path));
+    }
+
+    let known = file_path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ["mcpack", "mcaddon", "mctemplate"].contains(&ext.to_lowercase().as_str()));
+    if !known && !has_zip_magic(&file_path) {
+        return Err(format!("Not a Minecraft pack file: {}", path));
+    }
+
+    let icon_names = app.state::<AppState>().settings.read().icon_names();
+    let size = std::fs::metadata(&file_path).map(|m| m.len()).ok();
+    let mut packs = tokio::task::spawn_blocking(move || scan_single_pack(&file_path, &icon_names))
+        .await
+        .map_err(|e| format!("Scan failed: {}", e))?;
+
+    if packs.is_empty() {
+        return Err(format!("No packs found in {}", path));
+    }
+    if let Some(size) = size {
+        for pack in packs.iter_mut().filter(|p| p.folder_size.is_none()) {
+            pack.folder_size = Some(size);
+            pack.folder_size_formatted = Some(format_bytes(size));
+        }
+    }
+
+    apply_pack_tags(&app, &mut packs);
+
+    emit_log(&app, "INFO", &format!("Scanned {}: {} packs", path, packs.len()));
+    Ok(packs)
+}
+
+/// Marks `pack` installed, or as an update, against the install `lookup`
+/// matched it to, comparing versions and falling back to folder sizes.
+fn apply_install_status(
+    pack: &mut PackInfo,
+    installed: &InstalledPackInfo,
+    update_size_threshold: f64,
+    size_cache: &mut std::collections::HashMap<String, u64>,
+) {
+    let uuid_match = pack.uuid.is_some() && pack.uuid == installed.uuid;
+
+    let new_ver: Option<String> = if uuid_match {
+        extract_version_from_name(&pack.name)
+            .or_else(|| extract_version_from_path(&pack.path))
+            .or_else(|| pack.version.clone())
+    } else {
+        pack.version.clone()
+            .or_else(|| extract_version_from_name(&pack.name))
+            .or_else(|| extract_version_from_path(&pack.path))
+    };
+
+    let old_ver: Option<String> = if uuid_match {
+        extract_version_from_name(&installed.folder_name)
+            .or_else(|| extract_version_from_path(&installed.path))
+            .or_else(|| installed.version.clone())
+    } else {
+        installed.version.clone()
+            .or_else(|| extract_version_from_name(&installed.name))
+            .or_else(|| extract_version_from_path(&installed.path))
+    };
+
+    match (new_ver.clone(), old_ver.clone()) {
+        (Some(new_version), Some(old_version)) => {
+            if new_version == old_version {
+                pack.is_installed = Some(true);
+                pack.installed_version = Some(old_version);
+            } else {
+                pack.is_installed = Some(true);
+                pack.is_update = Some(true);
+                pack.installed_version = Some(old_version);
+            }
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            pack.is_installed = Some(true);
+            pack.installed_version = old_ver.clone();
+        }
+        // Fallback only when neither side has any version info: treat a
+        // clearly larger source as an update. A smaller source is more likely
+        // a different or trimmed pack, so shrinking never counts.
+        (None, None) => {
+            pack.is_installed = Some(true);
+            let old_size = size_cache.entry(installed.path.clone()).or_insert_with(|| {
+                let path = std::path::Path::new(&installed.path);
+                calculate_folder_size(path)
+            });
+            if let Some(new_size) = pack.folder_size {
+                if *old_size > 0
+                    && new_size > *old_size
+                    && new_size as f64 / *old_size as f64 > update_size_threshold
+                {
+                    pack.is_update = Some(true);
+                }
+            }
+        }
+    }
+}
+
+fn update_size_threshold(app: &AppHandle) -> f64 {
+    app.state::<AppState>().settings.read()
+        .update_size_threshold
+        .filter(|t| t.is_finite() && *t >= 1.0)
+        .unwrap_or(1.1)
+}
+
 #[tauri::command]
 async fn compute_pack_status(packs: Vec<PackInfo>, app: AppHandle) -> Result<Vec<PackInfo>, String> {
     let app_for_emit = app.clone();
-    tokio::task::spawn_blocking(move || {
+    let total_packs = packs.len();
+    let _ = app.emit("progress", serde_json::json!({
+        "current": 0,
+        "total": total_packs,
+        "message": "Checking install status...",
+        "phase": "statusing"
+    }));
+    
+    let update_size_threshold = update_size_threshold(&app);
+    
+    let results = tokio::task::spawn_blocking(move || {
         let installed_packs = get_installed_packs_info(&app_for_emit);
-        let installed_by_uuid: std::collections::HashMap<&str, usize> = installed_packs
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, ip)| ip.uuid.as_deref().map(|u| (u, idx)))
-            .collect();
-        let installed_base_names: std::collections::HashMap<(PackType, String), usize> = installed_packs
-            .iter()
-            .enumerate()
-            .map(|(idx, ip)| ((ip.pack_type, extract_base_name(&ip.name)), idx))
-            .collect();
+        let lookup = InstalledLookup::new(&installed_packs);
         let mut size_cache: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
         let mut results = packs;
 
         for pack in &mut results {
-            let installed_index = if let Some(uuid) = pack.uuid.as_deref() {
-                installed_by_uuid.get(uuid).copied()
-            } else {
-                let pack_base = extract_base_name(&pack.name);
-                installed_base_names.get(&(pack.pack_type, pack_base)).copied()
+            if let Some(idx) = lookup.find(pack) {
+                apply_install_status(pack, &installed_packs[idx], update_size_threshold, &mut size_cache);
+            }
+        }
+
+        // format_version 1 manifests use the legacy layout newer clients may skip
+        for pack in results.iter_mut().filter(|p| p.format_version == Some(1)) {
+            if !pack.attention_reasons.contains(&AttentionReason::LegacyFormat) {
+                pack.attention_reasons.push(AttentionReason::LegacyFormat);
+                pack.needs_attention = Some(true);
+                pack.attention_message = attention_message_for(&pack.attention_reasons);
+            }
+        }
+
+        let installed_uuids: std::collections::HashSet<String> = lookup.by_uuid
+            .keys()
+            .map(|u| u.to_lowercase())
+            .collect();
+        link_dependent_packs(&mut results, &installed_uuids);
+
+        results
+    })
+    .await
+    .map_err(|e| format!("Status check failed: {}", e))?;
+    
+    let _ = app.emit("progress", serde_json::json!({
+        "current": total_packs,
+        "total": total_packs,
+        "message": "Status check complete",
+        "phase": "done"
+    }));
+    
+    Ok(results)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledStatus {
+    /// "installed", "not_installed" or "update_available"
+    pub status: String,
+    pub installed_version: Option<String>,
+    pub installed_path: Option<String>,
+}
+
+/// Single-pack fast path of compute_pack_status, e.g. for a dropped file.
+#[tauri::command]
+async fn is_pack_installed(pack: PackInfo, app: AppHandle) -> Result<InstalledStatus, String> {
+    let update_size_threshold = update_size_threshold(&app);
+    tokio::task::spawn_blocking(move || {
+        let installed_packs = get_installed_packs_info(&app);
+        let Some(idx) = InstalledLookup::new(&installed_packs).find(&pack) else {
+            return InstalledStatus {
+                status: "not_installed".to_string(),
+                installed_version: None,
+                installed_path: None,
             };
+        };
 
-            if let Some(idx) = installed_index {
-                let installed = &installed_packs[idx];
-                let uuid_match = pack.uuid.is_some() && pack.uuid == installed.uuid;
+        let installed = &installed_packs[idx];
+        let mut pack = pack;
+        apply_install_status(&mut pack, installed, update_size_threshold, &mut std::collections::HashMap::new());
+        let status = if pack.is_update == Some(true) { "update_available" } else { "installed" };
+        InstalledStatus {
+            status: status.to_string(),
+            installed_version: pack.installed_version,
+            installed_path: Some(installed.path.clone()),
+        }
+    })
+    .await
+    .map_err(|e| format!("Status check failed: {}", e))
+}
 
-                let new_ver: Option<String> = if uuid_match {
-                    extract_version_from_name(&pack.name)
-                        .or_else(|| extract_version_from_path(&pack.path))
-                        .or_else(|| pack.version.clone())
-                } else {
-                    pack.version.clone()
-                        .or_else(|| extract_version_from_name(&pack.name))
-                        .or_else(|| extract_version_from_path(&pack.path))
-                };
+/// Updates per pack type, keyed by its display name, plus a "new" count of
+/// packs that aren't installed. Expects packs already run through
+/// compute_pack_status.
+#[tauri::command]
+fn update_counts(packs: Vec<PackInfo>) -> std::collections::HashMap<String, usize> {
+    let mut counts = std::collections::HashMap::new();
+    for pack in &packs {
+        if pack.is_update == Some(true) {
+            *counts.entry(pack.pack_type.to_string()).or_insert(0) += 1;
+        } else if pack.is_installed != Some(true) {
+            *counts.entry("new".to_string()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
 
-                let old_ver: Option<String> = if uuid_match {
-                    extract_version_from_name(&installed.folder_name)
-                        .or_else(|| extract_version_from_path(&installed.path))
-                        .or_else(|| installed.version.clone())
-                } else {
-                    installed.version.clone()
-                        .or_else(|| extract_version_from_name(&installed.name))
-                        .or_else(|| extract_version_from_path(&installed.path))
-                };
+/// Uncompressed size `pack` will take once extracted.
+fn incoming_pack_size(pack: &PackInfo) -> Result<u64, String> {
+    if let Some(size) = pack.uncompressed_size {
+        return Ok(size);
+    }
+    let prefix = pack.subfolder.as_deref()
+        .map(|s| format!("{}/", s.trim_end_matches(['/', '\\']).replace('\\', "/")))
+        .unwrap_or_default();
+    Ok(list_pack_files(std::path::Path::new(&pack.path))?
+        .into_iter()
+        .filter(|(rel, _)| rel.starts_with(&prefix))
+        .map(|(_, size)| size)
+        .sum())
+}
 
-                match (new_ver.clone(), old_ver.clone()) {
-                    (Some(new_version), Some(old_version)) => {
-                        if new_version == old_version {
-                            pack.is_installed = Some(true);
-                            pack.installed_version = Some(old_version);
-                        } else {
-                            pack.is_installed = Some(true);
-                            pack.is_update = Some(true);
-                            pack.installed_version = Some(old_version);
-                        }
-                    }
-                    (Some(_), None) | (None, Some(_)) => {
-                        pack.is_installed = Some(true);
-                        pack.installed_version = old_ver.clone();
-                    }
-                    (None, None) => {
-                        pack.is_installed = Some(true);
-                        let old_size = size_cache.entry(installed.path.clone()).or_insert_with(|| {
-                            let path = std::path::Path::new(&installed.path);
-                            calculate_folder_size(path)
-                        });
-                        if let Some(new_size) = pack.folder_size {
-                            let size_diff = if new_size > *old_size {
-                                new_size as f64 / *old_size as f64
-                            } else {
-                                *old_size as f64 / new_size as f64
-                            };
-                            if size_diff > 1.1 {
-                                pack.is_update = Some(true);
-                            }
-                        }
-                    }
+/// Net disk change of installing `pack` over the install compute_pack_status
+/// would match it to: uncompressed incoming size minus the installed folder.
+#[tauri::command]
+async fn update_size_delta(pack: PackInfo, app: AppHandle) -> Result<(i64, String), String> {
+    tokio::task::spawn_blocking(move || {
+        let new_size = incoming_pack_size(&pack)?;
+        let installed_packs = get_installed_packs_info(&app);
+        let old_size = InstalledLookup::new(&installed_packs)
+            .find(&pack)
+            .map(|idx| calculate_folder_size(std::path::Path::new(&installed_packs[idx].path)))
+            .unwrap_or(0);
+
+        let delta = new_size as i64 - old_size as i64;
+        let sign = if delta < 0 { "-" } else { "+" };
+        Ok((delta, format!("{}{}", sign, format_bytes(delta.unsigned_abs()))))
+    })
+    .await
+    .map_err(|e| format!("Size check failed: {}", e))?
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackPlan {
+    pub path: String,
+    pub name: String,
+    pub pack_type: PackType,
+    pub destination: Option<String>,
+    pub uncompressed_size: u64,
+    pub is_update: bool,
+    pub net_delta: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrivePlan {
+    pub drive: String,
+    pub destinations: Vec<String>,
+    pub incoming_size: u64,
+    pub incoming_size_formatted: String,
+    pub net_size: i64,
+    pub free_space: Option<u64>,
+    pub free_space_formatted: Option<String>,
+    pub will_fit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPlan {
+    pub drives: Vec<DrivePlan>,
+    pub packs: Vec<PackPlan>,
+    pub will_fit: bool,
+}
+
+/// Pre-flight summary for process_packs: how much each destination drive
+/// receives, how much it has free, and which packs replace an install.
+#[tauri::command]
+async fn plan_import(packs: Vec<PackInfo>, app: AppHandle) -> Result<ImportPlan, String> {
+    let settings = app.state::<AppState>().settings.read().clone();
+    let destinations: Vec<Option<String>> = packs
+        .iter()
+        .map(|p| get_destination_for_pack_type(p.pack_type, app.clone()).filter(|d| !d.is_empty()))
+        .collect();
+
+    tokio::task::spawn_blocking(move || {
+        let installed_packs = get_installed_packs_info(&app);
+        let lookup = InstalledLookup::new(&installed_packs);
+        // Archived old versions stay on the same drive, so updates free nothing
+        let keeps_old_versions = settings.keep_old_versions.is_some_and(|n| n > 0);
+
+        let mut plans = Vec::with_capacity(packs.len());
+        for (pack, destination) in packs.iter().zip(destinations) {
+            let uncompressed_size = incoming_pack_size(pack)?;
+            let installed = lookup.find(pack);
+            let old_size = match installed {
+                Some(idx) if !keeps_old_versions => calculate_folder_size(std::path::Path::new(&installed_packs[idx].path)),
+                _ => 0,
+            };
+            plans.push(PackPlan {
+                path: pack.path.clone(),
+                name: pack.name.clone(),
+                pack_type: pack.pack_type,
+                destination,
+                uncompressed_size,
+                is_update: installed.is_some(),
+                net_delta: uncompressed_size as i64 - old_size as i64,
+            });
+        }
+
+        let mut drives: Vec<DrivePlan> = Vec::new();
+        for plan in &plans {
+            let Some(destination) = plan.destination.as_deref() else { continue };
+            let path = std::path::Path::new(destination);
+            let drive = drive_root(path).unwrap_or_else(|| destination.to_string());
+            let idx = match drives.iter().position(|d| d.drive == drive) {
+                Some(idx) => idx,
+                None => {
+                    let free_space = fs4::available_space(path).ok();
+                    drives.push(DrivePlan {
+                        drive,
+                        destinations: Vec::new(),
+                        incoming_size: 0,
+                        incoming_size_formatted: String::new(),
+                        net_size: 0,
+                        free_space,
+                        free_space_formatted: free_space.map(format_bytes),
+                        will_fit: true,
+                    });
+                    drives.len() - 1
                 }
+            };
+            let entry = &mut drives[idx];
+            if !entry.destinations.iter().any(|d| d == destination) {
+                entry.destinations.push(destination.to_string());
             }
+            entry.incoming_size += plan.uncompressed_size;
+            entry.net_size += plan.net_delta;
+        }
+        for drive in &mut drives {
+            drive.incoming_size_formatted = format_bytes(drive.incoming_size);
+            drive.will_fit = match drive.free_space {
+                Some(free) => drive.net_size <= 0 || (drive.net_size as u64) <= free,
+                None => true,
+            };
         }
 
-        results
+        Ok(ImportPlan {
+            will_fit: drives.iter().all(|d| d.will_fit),
+            drives,
+            packs: plans,
+        })
     })
     .await
-    .map_err(|e| format!("Status check failed: {}", e))
+    .map_err(|e| format!("Import planning failed: {}", e))?
+}
+
+/// Points each pack that depends on another scanned pack (e.g. an HD texture addon)
+/// at that base pack, and flags dependencies that are neither scanned nor installed.
+fn link_dependent_packs(packs: &mut [PackInfo], installed_uuids: &std::collections::HashSet<String>) {
+    let scanned_uuids: std::collections::HashSet<String> = packs
+        .iter()
+        .filter_map(|p| p.uuid.as_ref().map(|u| u.to_lowercase()))
+        .collect();
+
+    for pack in packs.iter_mut() {
+        let own_uuid = pack.uuid.as_ref().map(|u| u.to_lowercase());
+        let mut missing = false;
+
+        for dep in &pack.dependencies {
+            if own_uuid.as_deref() == Some(dep.as_str()) {
+                continue;
+            }
+            if scanned_uuids.contains(dep) {
+                if pack.linked_to.is_none() {
+                    pack.linked_to = Some(dep.clone());
+                }
+            } else if !installed_uuids.contains(dep) {
+                missing = true;
+            }
+        }
+
+        if missing && !pack.attention_reasons.contains(&AttentionReason::DependencyMissing) {
+            pack.attention_reasons.push(AttentionReason::DependencyMissing);
+            pack.needs_attention = Some(true);
+            pack.attention_message = attention_message_for(&pack.attention_reasons);
+        }
+    }
+}
+
+/// Orders packs so every pack comes after the scanned packs it depends on.
+/// Packs caught in a dependency cycle keep their original relative order.
+fn order_by_dependencies(packs: Vec<PackInfo>) -> Vec<PackInfo> {
+    let uuid_index: std::collections::HashMap<String, usize> = packs
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, p)| p.uuid.as_ref().map(|u| (u.to_lowercase(), idx)))
+        .collect();
+
+    let mut pending: Vec<usize> = vec![0; packs.len()];
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); packs.len()];
+    for (idx, pack) in packs.iter().enumerate() {
+        for dep in &pack.dependencies {
+            if let Some(&dep_idx) = uuid_index.get(dep) {
+                if dep_idx != idx {
+                    pending[idx] += 1;
+                    dependents[dep_idx].push(idx);
+                }
+            }
+        }
+    }
+
+    let mut order = Vec::with_capacity(packs.len());
+    let mut placed = vec![false; packs.len()];
+    let mut ready: std::collections::VecDeque<usize> =
+        (0..packs.len()).filter(|&idx| pending[idx] == 0).collect();
+    while let Some(idx) = ready.pop_front() {
+        placed[idx] = true;
+        order.push(idx);
+        for &next in &dependents[idx] {
+            pending[next] -= 1;
+            if pending[next] == 0 {
+                ready.push_back(next);
+            }
+        }
+    }
+    order.extend((0..packs.len()).filter(|&idx| !placed[idx]));
+
+    let mut slots: Vec<Option<PackInfo>> = packs.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|idx| slots[idx].take()).collect()
+}
+
+fn create_mover(settings: Settings, app: &AppHandle) -> FileMover {
+    let (log_tx, mut log_rx) = mpsc::unbounded_channel();
+
+    let history = app.state::<AppState>().history.clone();
+    let mut mover = FileMover::with_history(settings, history);
+    mover.set_log_sender(log_tx);
+
+    let app_clone = app.clone();
+    tokio::spawn(async move {
+        while let Some(log) = log_rx.recv().await {
+            let _ = app_clone.emit("log", log);
+        }
+    });
+
+    mover
+}
+
+#[tauri::command]
+async fn install_pack_group(packs: Vec<PackInfo>, app: AppHandle) -> Result<Vec<MoveOperation>, String> {
+    let settings = app.state::<AppState>().settings.read().clone();
+    if !settings.dry_run {
+        ensure_not_safe_mode(&app)?;
+    }
+    let packs = order_by_dependencies(packs);
+    let total = packs.len();
+
+    emit_log(&app, "INFO", &format!("Installing pack group of {} packs, dependencies first", total));
+
+    let mut mover = create_mover(settings.clone(), &app);
+    if settings.skip_identical.unwrap_or(false) {
+        mover.set_prior_history(load_history_from_file());
+    }
+    let scan_dir = settings.scan_location.as_ref().map(PathBuf::from);
+
+    let mut results = Vec::with_capacity(total);
+    for (idx, pack) in packs.iter().enumerate() {
+        let _ = app.emit("progress", serde_json::json!({
+            "current": idx + 1,
+            "total": total,
+            "message": format!("Processing {}", pack.name)
+        }));
+        results.push(mover.process_pack(pack, scan_dir.as_ref(), Some(idx)).await);
+    }
+
+    if !settings.dry_run {
+        if let Err(e) = append_history_to_file(&mover.recorded_operations()) {
+            emit_log(&app, "WARN", &format!("Failed to save install history: {}", e));
+        }
+    }
+
+    let _ = app.emit("progress", serde_json::json!({
+        "current": total,
+        "total": total,
+        "message": "Complete"
+    }));
+
+    Ok(results)
 }
 
 #[tauri::command]
-async fn process_packs(packs: Vec<PackInfo>, app: AppHandle) -> Result<Vec<MoveOperation>, String> {
+async fn process_packs(mut packs: Vec<PackInfo>, app: AppHandle) -> Result<Vec<MoveOperation>, String> {
     let state = app.state::<AppState>();
     let settings = state.settings.read().clone();
+    if !settings.dry_run {
+        ensure_not_safe_mode(&app)?;
+        warn_if_minecraft_running_async(&app).await;
+    }
     
     let total = packs.len();
-    let delete_source = settings.delete_source;
-    let (log_tx, mut log_rx) = mpsc::unbounded_channel();
+    let ordered_install = settings.ordered_install.unwrap_or(false);
     
-    let mut mover = FileMover::new(settings.clone());
-    mover.set_log_sender(log_tx);
+    if ordered_install {
+        packs.sort_by_key(|p| p.pack_type.install_rank());
+        emit_log(&app, "INFO", "Ordered install: behavior packs first, then resource packs, then templates and skins");
+    }
+    
+    let mut mover = create_mover(settings.clone(), &app);
+    if settings.skip_identical.unwrap_or(false) {
+        mover.set_prior_history(load_history_from_file());
+    }
     let mover = Arc::new(mover);
     
     let scan_dir = settings.scan_location.as_ref().map(|s| PathBuf::from(s));
     
-    let app_clone = app.clone();
-    tokio::spawn(async move {
-        while let Some(log) = log_rx.recv().await {
-            let _ = app_clone.emit("log", log);
-        }
-    });
-    
     let results = Arc::new(RwLock::new(Vec::new()));
-    let processed_sources = Arc::new(RwLock::new(Vec::new()));
     let counter = Arc::new(std::sync::atomic::AtomicUsize::new(0));
     
     let mut handles = Vec::new();
-    let max_concurrent = 8;
+    let max_concurrent = if ordered_install { 1 } else { 8 };
     let semaphore = Arc::new(tokio::sync::Semaphore::new(max_concurrent));
     
-    for pack in packs {
+    for (index, pack) in packs.into_iter().enumerate() {
         let mover_clone = Arc::clone(&mover);
         let scan_dir_clone = scan_dir.clone();
         let results_clone = Arc::clone(&results);
-        let processed_sources_clone = Arc::clone(&processed_sources);
         let counter_clone = Arc::clone(&counter);
         let app_clone = app.clone();
-        let semaphore_clone = Arc::clone(&semaphore);
-        let delete_source_clone = delete_source;
-        let source_path = pack.path.clone();
+        
+        // Acquire before spawning so packs start in the order they were queued
+        let permit = Arc::clone(&semaphore)
+            .acquire_owned()
+            .await
+            .map_err(|e| format!("Install queue closed: {}", e))?;
         
         let handle = tokio::spawn(async move {
-            let _permit = semaphore_clone.acquire().await.unwrap();
+            let _permit = permit;
             
             let current = counter_clone.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
             let _ = app_clone.emit("progress", serde_json::json!({
@@ -332,11 +841,7 @@ async fn process_packs(packs: Vec<PackInfo>, app: AppHandle) -> Result<Vec<MoveO
                 "message": format!("Processing {}", pack.name)
             }));
             
-            let result = mover_clone.process_pack(&pack, scan_dir_clone.as_ref()).await;
-            
-            if result.success && delete_source_clone {
-                processed_sources_clone.write().push(source_path);
-            }
+            let result = mover_clone.process_pack(&pack, scan_dir_clone.as_ref(), Some(index)).await;
             
             results_clone.write().push(result);
         });
@@ -350,11 +855,9 @@ async fn process_packs(packs: Vec<PackInfo>, app: AppHandle) -> Result<Vec<MoveO
     
     let mut final_results = Arc::try_unwrap(results).unwrap().into_inner();
     
-    if delete_source {
-        for source in Arc::try_unwrap(processed_sources).unwrap().into_inner() {
-            if std::fs::remove_file(&source).is_ok() {
-                emit_log(&app, "INFO", &format!("Deleted source file: {}", source));
-            }
+    if !settings.dry_run {
+        if let Err(e) = append_history_to_file(&mover.recorded_operations()) {
+            emit_log(&app, "WARN", &format!("Failed to save install history: {}", e));
         }
     }
     
@@ -364,35 +867,370 @@ async fn process_packs(packs: Vec<PackInfo>, app: AppHandle) -> Result<Vec<MoveO
         "message": "Complete"
     }));
     
-    final_results.sort_by(|a, b| a.pack_name.cmp(&b.pack_name));
+    if !ordered_install {
+        final_results.sort_by(|a, b| a.pack_name.cmp(&b.pack_name));
+    }
     Ok(final_results)
 }
 
+/// Installs one pack and opens the folder it landed in. Nothing is opened
+/// when the install fails.
+#[tauri::command]
+async fn install_and_reveal(pack: PackInfo, app: AppHandle) -> Result<MoveOperation, String> {
+    let operation = process_packs(vec![pack], app.clone())
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Install produced no result".to_string())?;
+
+    if !operation.success {
+        return Err(operation.error.unwrap_or_else(|| format!("Failed to install {}", operation.pack_name)));
+    }
+    // Dry runs report a destination that was never created
+    if std::path::Path::new(&operation.destination).exists() {
+        open_folder(operation.destination.clone())?;
+    }
+    Ok(operation)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackCollection {
+    name: String,
+    uuids: Vec<String>,
+    created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CollectionInstall {
+    operations: Vec<MoveOperation>,
+    missing: Vec<String>,
+}
+
+fn collections_dir() -> Option<PathBuf> {
+    app_config_dir().map(|d| d.join("collections"))
+}
+
+fn collection_file(name: &str) -> Result<PathBuf, String> {
+    let dir = collections_dir().ok_or_else(|| "Could not determine config directory".to_string())?;
+    Ok(dir.join(format!("{}.json", sanitize_folder_name(name))))
+}
+
+#[tauri::command]
+fn save_collection(name: String, uuids: Vec<String>, app: AppHandle) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Collection name is empty".to_string());
+    }
+    if uuids.is_empty() {
+        return Err("Collection has no packs".to_string());
+    }
+
+    let path = collection_file(&name)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create collections folder: {}", e))?;
+    }
+    let collection = PackCollection {
+        name: name.clone(),
+        uuids,
+        created_at: chrono::Local::now().to_rfc3339(),
+    };
+    let content = serde_json::to_string_pretty(&collection).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to save collection: {}", e))?;
+
+    emit_log(&app, "INFO", &format!("Saved collection '{}' with {} packs", name, collection.uuids.len()));
+    Ok(())
+}
+
+#[tauri::command]
+fn list_collections() -> Vec<String> {
+    let Some(dir) = collections_dir() else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+                .filter_map(|content| serde_json::from_str::<PackCollection>(&content).ok())
+                .map(|c| c.name)
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_by_key(|n| n.to_lowercase());
+    names
+}
+
+#[tauri::command]
+async fn install_collection(name: String, app: AppHandle) -> Result<CollectionInstall, String> {
+    let path = collection_file(&name)?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| format!("Collection '{}' not found", name))?;
+    let collection: PackCollection = serde_json::from_str(&content)
+        .map_err(|e| format!("Collection '{}' is corrupt: {}", name, e))?;
+
+    let scan_location = app.state::<AppState>().settings.read().scan_location.clone()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "No scan location configured".to_string())?;
+    let scanned = scan_packs(scan_location, app.clone()).await?;
+
+    let mut found: Vec<PackInfo> = Vec::new();
+    let mut missing = Vec::new();
+    for uuid in &collection.uuids {
+        let matched = scanned.iter().find(|p| {
+            p.uuid.as_deref().is_some_and(|u| u.eq_ignore_ascii_case(uuid))
+        });
+        match matched {
+            Some(pack) if !found.iter().any(|f| f.path == pack.path) => found.push(pack.clone()),
+            Some(_) => {}
+            None => missing.push(uuid.clone()),
+        }
+    }
+
+    if !missing.is_empty() {
+        emit_log(&app, "WARN", &format!(
+            "Collection '{}': {} packs not found in the scan folder", collection.name, missing.len()
+        ));
+    }
+    emit_log(&app, "INFO", &format!("Installing collection '{}' ({} packs)", collection.name, found.len()));
+
+    let operations = if found.is_empty() {
+        Vec::new()
+    } else {
+        process_packs(found, app).await?
+    };
+    Ok(CollectionInstall { operations, missing })
+}
+
+/// Re-runs only the failed packs of an earlier batch. Failures are matched back
+/// to their PackInfo by source path and type, since pack_name carries the folder suffix.
+#[tauri::command]
+async fn retry_failed(operations: Vec<MoveOperation>, packs: Vec<PackInfo>, app: AppHandle) -> Result<Vec<MoveOperation>, String> {
+    let failed: std::collections::HashSet<(String, PackType)> = operations
+        .iter()
+        .filter(|op| !op.success)
+        .map(|op| (op.source.clone(), op.pack_type))
+        .collect();
+    if failed.is_empty() {
+        return Ok(operations);
+    }
+
+    let retry: Vec<PackInfo> = packs
+        .into_iter()
+        .filter(|p| failed.contains(&(p.path.clone(), p.pack_type)))
+        .collect();
+    emit_log(&app, "INFO", &format!("Retrying {} failed packs", retry.len()));
+
+    let retried = process_packs(retry, app).await?;
+    let retried_keys: std::collections::HashSet<(String, PackType)> = retried
+        .iter()
+        .map(|op| (op.source.clone(), op.pack_type))
+        .collect();
+
+    let mut combined: Vec<MoveOperation> = operations
+        .into_iter()
+        .filter(|op| op.success || !retried_keys.contains(&(op.source.clone(), op.pack_type)))
+        .collect();
+    combined.extend(retried);
+    Ok(combined)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DestinationStatus {
+    pub label: String,
+    pub path: Option<String>,
+    pub exists: bool,
+    pub writable: bool,
+    pub free_space: Option<u64>,
+    pub free_space_formatted: Option<String>,
+    pub error: Option<String>,
+}
+
+fn check_destination(label: &str, path_opt: Option<String>) -> DestinationStatus {
+    let mut status = DestinationStatus {
+        label: label.to_string(),
+        path: path_opt.clone(),
+        exists: false,
+        writable: false,
+        free_space: None,
+        free_space_formatted: None,
+        error: None,
+    };
+    
+    let path_str = match path_opt {
+        Some(p) => p,
+        None => {
+            status.error = Some("Not configured".to_string());
+            return status;
+        }
+    };
+    
+    let path = std::path::Path::new(&path_str);
+    status.exists = path.is_dir();
+    if !status.exists {
+        status.error = Some("Folder does not exist".to_string());
+        return status;
+    }
+    
+    if let Ok(free) = fs4::available_space(path) {
+        status.free_space = Some(free);
+        status.free_space_formatted = Some(format_bytes(free));
+    }
+    
+    let probe = path.join(format!(".blocksmith-write-test-{}", uuid::Uuid::new_v4()));
+    let write_result = std::fs::write(&probe, b"blocksmith");
+    // Always try to clean up, even if the write only partially succeeded
+    let _ = std::fs::remove_file(&probe);
+    
+    match write_result {
+        Ok(()) => status.writable = true,
+        Err(e) => status.error = Some(format!("Folder is not writable: {}", e)),
+    }
+    
+    status
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PathClassification {
+    label: String,
+    path: String,
+    kind: DriveKind,
+}
+
+#[tauri::command]
+async fn classify_paths(app: AppHandle) -> Result<Vec<PathClassification>, String> {
+    let settings = app.state::<AppState>().settings.read().clone();
+    let paths = [
+        ("Behavior Packs", settings.behavior_pack_path),
+        ("Resource Packs", settings.resource_pack_path),
+        ("Skin Packs", settings.skin_pack_path),
+        ("4D Skin Packs", settings.skin_pack_4d_path),
+        ("World Templates", settings.world_template_path),
+        ("Scan Location", settings.scan_location),
+    ];
+
+    tokio::task::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .filter_map(|(label, path)| path.filter(|p| !p.is_empty()).map(|p| (label, p)))
+            .map(|(label, path)| PathClassification {
+                label: label.to_string(),
+                kind: classify_drive(std::path::Path::new(&path)),
+                path,
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Path classification failed: {}", e))
+}
+
+#[tauri::command]
+async fn precheck_destinations(app: AppHandle) -> Result<Vec<DestinationStatus>, String> {
+    let settings = app.state::<AppState>().settings.read().clone();
+    
+    let mut destinations = vec![
+        ("Behavior Packs", settings.behavior_pack_path.clone()),
+        ("Resource Packs", settings.resource_pack_path.clone()),
+        ("Skin Packs", settings.skin_pack_path.clone()),
+        ("World Templates", settings.world_template_path.clone()),
+    ];
+    if settings.skin_pack_4d_path.is_some() {
+        destinations.push(("4D Skin Packs", settings.skin_pack_4d_path.clone()));
+    }
+    
+    tokio::task::spawn_blocking(move || {
+        destinations
+            .into_iter()
+            .map(|(label, path)| check_destination(label, path))
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Destination check failed: {}", e))
+}
+
 #[tauri::command]
 async fn rollback_last(app: AppHandle) -> Result<Option<MoveOperation>, String> {
     emit_log(&app, "INFO", "Attempting to rollback last operation");
     
     let state = app.state::<AppState>();
     let settings = state.settings.read().clone();
+    if !settings.dry_run {
+        ensure_not_safe_mode(&app)?;
+    }
     
-    let (log_tx, mut log_rx) = mpsc::unbounded_channel();
-    
-    let mut mover = FileMover::new(settings);
-    mover.set_log_sender(log_tx);
-    let mover = Arc::new(mover);
+    let mover = create_mover(settings, &app);
+    let result = mover.rollback_last().await;
     
-    let app_clone = app.clone();
-    tokio::spawn(async move {
-        while let Some(log) = log_rx.recv().await {
-            let _ = app_clone.emit("log", log);
+    if let Some(ref op) = result {
+        if op.reverted {
+            if let Err(e) = mark_reverted_in_history_file(op) {
+                emit_log(&app, "WARN", &format!("Failed to update install history: {}", e));
+            }
         }
-    });
-    
-    let result = mover.rollback_last().await;
+    }
     
     Ok(result)
 }
 
+/// Resolves `path` to a staged pack folder inside Settings.staging_dir.
+fn resolve_staged_path(path: &str, app: &AppHandle) -> Result<PathBuf, String> {
+    let staging_dir = app.state::<AppState>().settings.read().staging_dir.clone()
+        .filter(|d| !d.trim().is_empty())
+        .ok_or_else(|| "No staging folder configured".to_string())?;
+    let root = std::path::Path::new(&staging_dir).canonicalize()
+        .map_err(|_| format!("Staging folder not found: {}", staging_dir))?;
+    let staged = std::path::Path::new(path).canonicalize()
+        .map_err(|_| format!("Path does not exist: {}", path))?;
+    if staged == root || !staged.starts_with(&root) {
+        return Err("Path is not inside the staging folder".to_string());
+    }
+    if !staged.join(STAGED_FILE_NAME).is_file() {
+        return Err(format!("Not a staged pack: {}", path));
+    }
+    Ok(staged)
+}
+
+#[tauri::command]
+async fn commit_staged(path: String, app: AppHandle) -> Result<MoveOperation, String> {
+    ensure_not_safe_mode(&app)?;
+    let staged = resolve_staged_path(&path, &app)?;
+    // The staging record is a file on disk; don't let it aim the install elsewhere
+    let dest_base = staged_dest_base(&staged)?;
+    if !is_within_configured_dirs(&dest_base, &app) {
+        return Err(format!("Staged destination is outside the configured pack folders: {}", dest_base.display()));
+    }
+    let settings = app.state::<AppState>().settings.read().clone();
+    if !settings.dry_run {
+        warn_if_minecraft_running_async(&app).await;
+    }
+
+    let mover = create_mover(settings.clone(), &app);
+    let op = mover.commit_staged(&staged).await?;
+    if !settings.dry_run {
+        if let Err(e) = append_history_to_file(&mover.recorded_operations()) {
+            emit_log(&app, "WARN", &format!("Failed to save install history: {}", e));
+        }
+    }
+    Ok(op)
+}
+
+#[tauri::command]
+fn discard_staged(path: String, app: AppHandle) -> Result<(), String> {
+    ensure_not_safe_mode(&app)?;
+    let staged = resolve_staged_path(&path, &app)?;
+    std::fs::remove_dir_all(&staged).map_err(|e| format!("Failed to discard staged pack: {}", e))?;
+    emit_log(&app, "INFO", &format!("Discarded staged pack: {}", staged.display()));
+    Ok(())
+}
+
+#[tauri::command]
+fn get_recent_operations(limit: usize) -> Vec<MoveOperation> {
+    load_history_from_file()
+        .into_iter()
+        .rev()
+        .take(limit)
+        .collect()
+}
+
 #[tauri::command]
 fn get_settings(app: AppHandle) -> Settings {
     let state = app.state::<AppState>();
@@ -401,12 +1239,112 @@ fn get_settings(app: AppHandle) -> Settings {
 }
 
 #[tauri::command]
-fn save_settings(settings: Settings, app: AppHandle) -> Result<(), String> {
+fn save_settings(mut settings: Settings, app: AppHandle) -> Result<(), String> {
     let state = app.state::<AppState>();
+    // Safe mode only changes through set_safe_mode
+    settings.safe_mode = state.settings.read().safe_mode;
+    // Tags are edited through add_tag/remove_tag
+    settings.pack_tags = state.settings.read().pack_tags.clone();
+    validate_theme_background(&settings)?;
     *state.settings.write() = settings.clone();
     save_settings_to_file(&settings)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathRemap {
+    pub settings: Settings,
+    pub missing: Vec<String>,
+}
+
+/// `path` with `old_prefix` swapped for `new_prefix`, matching whole path
+/// components only so "D:\Games" doesn't rewrite "D:\Games2".
+fn remap_path_prefix(path: &str, old_prefix: &str, new_prefix: &str) -> Option<String> {
+    let old = old_prefix.trim_end_matches(['/', '\\']);
+    let rest = if cfg!(windows) {
+        path.get(..old.len()).filter(|head| head.eq_ignore_ascii_case(old)).map(|_| &path[old.len()..])
+    } else {
+        path.strip_prefix(old)
+    }?;
+    if !rest.is_empty() && !rest.starts_with(['/', '\\']) {
+        return None;
+    }
+    Some(format!("{}{}", new_prefix.trim_end_matches(['/', '\\']), rest))
+}
+
+/// Rewrites every configured pack folder under `old_prefix` to sit under
+/// `new_prefix`, e.g. after moving Minecraft to another drive, and saves.
+#[tauri::command]
+fn remap_paths(old_prefix: String, new_prefix: String, app: AppHandle) -> Result<PathRemap, String> {
+    if old_prefix.trim().is_empty() {
+        return Err("Old prefix must not be empty".to_string());
+    }
+    let state = app.state::<AppState>();
+    let mut settings = state.settings.read().clone();
+    let mut missing = Vec::new();
+    let mut remapped = 0;
+
+    for path in [
+        &mut settings.behavior_pack_path,
+        &mut settings.resource_pack_path,
+        &mut settings.skin_pack_path,
+        &mut settings.skin_pack_4d_path,
+        &mut settings.world_template_path,
+        &mut settings.scan_location,
+    ] {
+        let Some(new_path) = path.as_deref().and_then(|p| remap_path_prefix(p, &old_prefix, &new_prefix)) else {
+            continue;
+        };
+        if !std::path::Path::new(&new_path).is_dir() {
+            missing.push(new_path.clone());
+        }
+        *path = Some(new_path);
+        remapped += 1;
+    }
+
+    *state.settings.write() = settings.clone();
+    save_settings_to_file(&settings)?;
+    emit_log(&app, "INFO", &format!("Remapped {} paths from '{}' to '{}'", remapped, old_prefix, new_prefix));
+    for path in &missing {
+        emit_log(&app, "WARN", &format!("Remapped path does not exist: {}", path));
+    }
+    Ok(PathRemap { settings, missing })
+}
+
+/// Points every pack type folder at a subfolder of `root`, laid out like
+/// com.mojang (e.g. a dedicated server's packs directory), creating any that
+/// are missing, and saves.
+#[tauri::command]
+fn set_packs_root(root: String, app: AppHandle) -> Result<Settings, String> {
+    let root = root.trim().to_string();
+    if root.is_empty() {
+        return Err("Packs root must not be empty".to_string());
+    }
+    let status = check_destination("Packs root", Some(root.clone()));
+    if !status.writable {
+        return Err(status.error.unwrap_or_else(|| format!("Folder is not writable: {}", root)));
+    }
+
+    let root_path = std::path::Path::new(&root);
+    let state = app.state::<AppState>();
+    let mut settings = state.settings.read().clone();
+    for (subfolder, path) in [
+        ("behavior_packs", &mut settings.behavior_pack_path),
+        ("resource_packs", &mut settings.resource_pack_path),
+        ("skin_packs", &mut settings.skin_pack_path),
+        ("world_templates", &mut settings.world_template_path),
+    ] {
+        let dir = root_path.join(subfolder);
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        *path = Some(dir.to_string_lossy().to_string());
+    }
+
+    *state.settings.write() = settings.clone();
+    save_settings_to_file(&settings)?;
+    emit_log(&app, "INFO", &format!("Pack folders set under '{}'", root));
+    Ok(settings)
+}
+
 #[tauri::command]
 fn save_ui_scale(scale: u32, app: AppHandle) -> Result<(), String> {
     let state = app.state::<AppState>();
@@ -416,6 +1354,121 @@ fn save_ui_scale(scale: u32, app: AppHandle) -> Result<(), String> {
     save_settings_to_file(&settings)
 }
 
+fn sort_pack_list(packs: &mut [PackInfo], by: &str, desc: bool) {
+    let by_name = |a: &PackInfo, b: &PackInfo| a.name.to_lowercase().cmp(&b.name.to_lowercase());
+    let directed = |order: std::cmp::Ordering| if desc { order.reverse() } else { order };
+
+    match by {
+        "size" => packs.sort_by(|a, b| directed(a.folder_size.cmp(&b.folder_size).then_with(|| by_name(a, b)))),
+        "type" => packs.sort_by(|a, b| directed(a.pack_type.to_string().cmp(&b.pack_type.to_string()).then_with(|| by_name(a, b)))),
+        "date" => {
+            // Source file or folder modification time; packs without one sort by name after the rest
+            // in either direction
+            let modified: std::collections::HashMap<String, std::time::SystemTime> = packs
+                .iter()
+                .filter_map(|p| {
+                    std::fs::metadata(&p.path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .map(|t| (p.path.clone(), t))
+                })
+                .collect();
+            packs.sort_by(|a, b| match (modified.get(&a.path), modified.get(&b.path)) {
+                (Some(ta), Some(tb)) => directed(ta.cmp(tb).then_with(|| by_name(a, b))),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => by_name(a, b),
+            });
+        }
+        _ => packs.sort_by(|a, b| directed(by_name(a, b))),
+    }
+}
+
+#[tauri::command]
+fn sort_packs(mut packs: Vec<PackInfo>, by: String, desc: bool, app: AppHandle) -> Result<Vec<PackInfo>, String> {
+    if !matches!(by.as_str(), "name" | "size" | "type" | "date") {
+        return Err(format!("Unknown sort order: {}", by));
+    }
+
+    sort_pack_list(&mut packs, &by, desc);
+
+    let state = app.state::<AppState>();
+    let mut settings = state.settings.read().clone();
+    settings.sort_by = Some(by);
+    settings.sort_desc = Some(desc);
+    *state.settings.write() = settings.clone();
+    save_settings_to_file(&settings)?;
+
+    Ok(packs)
+}
+
+fn settings_path_fields(settings: &mut Settings) -> [(&'static str, &mut Option<String>); 6] {
+    [
+        ("behavior_pack_path", &mut settings.behavior_pack_path),
+        ("resource_pack_path", &mut settings.resource_pack_path),
+        ("skin_pack_path", &mut settings.skin_pack_path),
+        ("skin_pack_4d_path", &mut settings.skin_pack_4d_path),
+        ("world_template_path", &mut settings.world_template_path),
+        ("scan_location", &mut settings.scan_location),
+    ]
+}
+
+#[tauri::command]
+fn export_settings_profile(output: String, normalize: Option<bool>, app: AppHandle) -> Result<(), String> {
+    let mut settings = app.state::<AppState>().settings.read().clone();
+    
+    // Machine-specific absolute paths are dropped so the profile applies cleanly elsewhere
+    if normalize.unwrap_or(false) {
+        for (_, field) in settings_path_fields(&mut settings) {
+            *field = None;
+        }
+    }
+    
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|e| e.to_string())?;
+    std::fs::write(&output, content)
+        .map_err(|e| format!("Failed to write profile: {}", e))?;
+    
+    emit_log(&app, "INFO", &format!("Exported settings profile to '{}'", output));
+    Ok(())
+}
+
+#[tauri::command]
+fn import_settings_profile(path: String, app: AppHandle) -> Result<Settings, String> {
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read profile: {}", e))?;
+    let raw = serde_json::from_str::<serde_json::Value>(&content)
+        .map_err(|e| format!("Profile is not valid JSON: {}", e))?;
+    if !raw.is_object() {
+        return Err("Profile does not contain a settings object".to_string());
+    }
+    
+    let mut imported = migrate_settings(raw);
+    let state = app.state::<AppState>();
+    let mut current = state.settings.read().clone();
+    
+    // Paths that don't exist on this machine keep the current value instead
+    for ((name, imported_field), (_, current_field)) in settings_path_fields(&mut imported)
+        .into_iter()
+        .zip(settings_path_fields(&mut current))
+    {
+        match imported_field.as_deref() {
+            Some(p) if !std::path::Path::new(p).exists() => {
+                emit_log(&app, "WARN", &format!("Ignoring {} from profile, folder not found: {}", name, p));
+                *imported_field = current_field.take();
+            }
+            None => *imported_field = current_field.take(),
+            _ => {}
+        }
+    }
+    
+    *state.settings.write() = imported.clone();
+    save_settings_to_file(&imported)?;
+    
+    emit_log(&app, "SUCCESS", &format!("Imported settings profile from '{}'", path));
+    Ok(imported)
+}
+
 fn save_settings_to_file(settings: &Settings) -> Result<(), String> {
     let config_dir = dirs::config_dir()
         .ok_or_else(|| "Could not determine config directory".to_string())?;
@@ -436,27 +1489,159 @@ fn save_settings_to_file(settings: &Settings) -> Result<(), String> {
 }
 
 fn load_settings_from_file() -> Settings {
-    if let Some(config_dir) = dirs::config_dir() {
-        let settings_path = config_dir.join("blocksmith").join("settings.json");
-        
-        if settings_path.exists() {
-            if let Ok(content) = std::fs::read_to_string(&settings_path) {
-                if let Ok(mut settings) = serde_json::from_str::<Settings>(&content) {
-                    // Reconcile background_style with theme so a mismatch never persists
-                    let is_minecraft = settings.theme.as_deref() == Some("minecraft");
-                    let bg = settings.background_style.as_deref().unwrap_or("");
-                    if is_minecraft && (bg == "embers" || bg == "matrix") {
-                        settings.background_style = Some("mc-terrain".to_string());
-                    } else if !is_minecraft && (bg == "mc-terrain" || bg == "minecraft") {
-                        settings.background_style = Some("embers".to_string());
-                    }
-                    return settings;
-                }
+    load_settings_with_recovery().0
+}
+
+/// Loads settings.json, migrating older schemas and repairing it field-by-field
+/// when it fails to parse. Returns a note describing any recovery that took place.
+fn load_settings_with_recovery() -> (Settings, Option<String>) {
+    let settings_path = match app_config_dir() {
+        Some(dir) => dir.join("settings.json"),
+        None => return (auto_detect_mc_paths(), None),
+    };
+    
+    if !settings_path.exists() {
+        return (auto_detect_mc_paths(), None);
+    }
+    
+    let content = match std::fs::read_to_string(&settings_path) {
+        Ok(content) => content,
+        Err(_) => return (auto_detect_mc_paths(), None),
+    };
+    
+    let raw = serde_json::from_str::<serde_json::Value>(&content).ok();
+    let parses_cleanly = raw.as_ref()
+        .map(|r| serde_json::from_value::<Settings>(r.clone()).is_ok())
+        .unwrap_or(false);
+    
+    if parses_cleanly {
+        if let Some(raw) = raw {
+            return (migrate_settings(raw), None);
+        }
+    }
+    
+    let backup_path = settings_path.with_extension("json.bak");
+    let backed_up = std::fs::copy(&settings_path, &backup_path).is_ok();
+    let backup_note = if backed_up {
+        format!("the original was backed up to {}", backup_path.display())
+    } else {
+        "the original could not be backed up".to_string()
+    };
+    
+    match raw {
+        Some(raw) if raw.is_object() => (
+            migrate_settings(raw),
+            Some(format!("settings.json contained invalid values and was repaired; {}", backup_note)),
+        ),
+        _ => (
+            auto_detect_mc_paths(),
+            Some(format!("settings.json could not be parsed and was reset; {}", backup_note)),
+        ),
+    }
+}
+
+const SETTINGS_VERSION: u32 = 1;
+
+/// Ordered schema migrations, each applied when the stored version is below its target.
+const SETTINGS_MIGRATIONS: &[(u32, fn(&mut serde_json::Value))] = &[
+    (1, migrate_v1_background_style),
+];
+
+fn migrate_v1_background_style(raw: &mut serde_json::Value) {
+    // "minecraft" was renamed to "mc-terrain"
+    if raw.get("background_style").and_then(|v| v.as_str()) == Some("minecraft") {
+        raw["background_style"] = serde_json::Value::String("mc-terrain".to_string());
+    }
+}
+
+fn migrate_settings(mut raw: serde_json::Value) -> Settings {
+    let stored_version = raw.get("settings_version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32;
+    
+    for (target_version, migration) in SETTINGS_MIGRATIONS {
+        if stored_version < *target_version {
+            migration(&mut raw);
+        }
+    }
+    
+    let mut settings = serde_json::from_value::<Settings>(raw.clone())
+        .ok()
+        .or_else(|| parse_settings_lenient(raw))
+        .unwrap_or_default();
+    
+    reconcile_theme_background(&mut settings);
+    settings.settings_version = Some(SETTINGS_VERSION);
+    settings
+}
+
+/// Keeps every field from `raw` that deserializes cleanly and falls back to the
+/// default for any field with an invalid value.
+fn parse_settings_lenient(raw: serde_json::Value) -> Option<Settings> {
+    let raw = raw.as_object()?;
+    let mut merged = match serde_json::to_value(Settings::default()).ok()? {
+        serde_json::Value::Object(map) => map,
+        _ => return None,
+    };
+    
+    for (key, value) in raw {
+        let previous = merged.insert(key.clone(), value.clone());
+        if serde_json::from_value::<Settings>(serde_json::Value::Object(merged.clone())).is_err() {
+            match previous {
+                Some(prev) => { merged.insert(key.clone(), prev); }
+                None => { merged.remove(key); }
             }
         }
     }
     
-    auto_detect_mc_paths()
+    serde_json::from_value::<Settings>(serde_json::Value::Object(merged)).ok()
+}
+
+/// Each theme with the background styles it supports, its default first.
+const THEMES: &[(&str, &[&str])] = &[
+    ("darkred", &["embers", "matrix", "none"]),
+    ("minecraft", &["mc-terrain", "night-sky", "none"]),
+];
+
+/// Background styles allowed for `theme`; an unset theme means darkred.
+fn theme_backgrounds(theme: Option<&str>) -> Option<&'static [&'static str]> {
+    let theme = theme.unwrap_or(THEMES[0].0);
+    THEMES.iter().find(|(name, _)| *name == theme).map(|(_, backgrounds)| *backgrounds)
+}
+
+/// Rejects an unknown theme, or a background style that doesn't belong to it.
+fn validate_theme_background(settings: &Settings) -> Result<(), String> {
+    let backgrounds = theme_backgrounds(settings.theme.as_deref())
+        .ok_or_else(|| format!("Unknown theme: {}", settings.theme.as_deref().unwrap_or_default()))?;
+    match settings.background_style.as_deref() {
+        Some(bg) if !backgrounds.contains(&bg) => Err(format!(
+            "Background style '{}' is not available for the {} theme",
+            bg,
+            settings.theme.as_deref().unwrap_or(THEMES[0].0)
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn reconcile_theme_background(settings: &mut Settings) {
+    // Reconcile background_style with theme so a mismatch never persists
+    let backgrounds = match theme_backgrounds(settings.theme.as_deref()) {
+        Some(backgrounds) => backgrounds,
+        None => {
+            settings.theme = None;
+            THEMES[0].1
+        }
+    };
+    if let Some(bg) = settings.background_style.as_deref() {
+        if !backgrounds.contains(&bg) {
+            settings.background_style = Some(backgrounds[0].to_string());
+        }
+    }
+}
+
+#[tauri::command]
+fn list_available_themes() -> Vec<String> {
+    THEMES.iter().map(|(name, _)| name.to_string()).collect()
 }
 
 fn auto_detect_mc_paths() -> Settings {
@@ -512,12 +1697,29 @@ fn auto_detect_mc_paths() -> Settings {
         }
     }
     
+    // 4D skin packs are handed to SkinMaster rather than the game, so look for an
+    // existing "4D Skin Packs" folder next to the downloads instead of under com.mojang
+    let four_d_name = settings.skin_4d_subfolder().to_string();
+    let four_d_candidates = [
+        settings.scan_location.as_ref().map(|s| std::path::PathBuf::from(s).join(&four_d_name)),
+        dirs::download_dir().map(|d| d.join(&four_d_name)),
+        dirs::document_dir().map(|d| d.join(&four_d_name)),
+    ];
+    settings.skin_pack_4d_path = four_d_candidates
+        .into_iter()
+        .flatten()
+        .find(|p| p.is_dir())
+        .map(|p| p.to_string_lossy().into_owned());
+    
     settings
 }
 
 #[tauri::command]
 fn load_settings(app: AppHandle) -> Settings {
-    let settings = load_settings_from_file();
+    let (settings, recovery_note) = load_settings_with_recovery();
+    if let Some(note) = recovery_note {
+        emit_log(&app, "WARN", &note);
+    }
     let state = app.state::<AppState>();
     *state.settings.write() = settings.clone();
     settings
@@ -525,15 +1727,18 @@ fn load_settings(app: AppHandle) -> Settings {
 
 #[tauri::command]
 fn get_destination_for_pack_type(pack_type: PackType, app: AppHandle) -> Option<String> {
-    let state = app.state::<AppState>();
-    let settings = state.settings.read();
-    
+    destination_for_pack_type(&app.state::<AppState>().settings.read(), pack_type)
+}
+
+fn destination_for_pack_type(settings: &Settings, pack_type: PackType) -> Option<String> {
     match pack_type {
         PackType::BehaviorPack => settings.behavior_pack_path.clone(),
         PackType::ResourcePack => settings.resource_pack_path.clone(),
         PackType::SkinPack => settings.skin_pack_path.clone(),
-        PackType::SkinPack4D => settings.scan_location.as_ref().map(|s| {
-            std::path::PathBuf::from(s).join("4D Skin Packs").to_string_lossy().into_owned()
+        PackType::SkinPack4D => settings.skin_pack_4d_path.clone().filter(|p| !p.is_empty()).or_else(|| {
+            settings.scan_location.as_ref().map(|s| {
+                std::path::PathBuf::from(s).join(settings.skin_4d_subfolder()).to_string_lossy().into_owned()
+            })
         }),
         PackType::WorldTemplate | PackType::MashupPack => settings.world_template_path.clone(),
         PackType::Unknown => None,
@@ -549,6 +1754,10 @@ fn open_folder(path: String) -> Result<(), String> {
         path
     };
     
+    open_in_file_manager(target)
+}
+
+fn open_in_file_manager(target: &std::path::Path) -> Result<(), String> {
     let target_str = target.to_string_lossy().to_string();
     
     #[cfg(target_os = "windows")]
@@ -578,6 +1787,27 @@ fn open_folder(path: String) -> Result<(), String> {
     Ok(())
 }
 
+fn app_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("blocksmith"))
+}
+
+#[tauri::command]
+fn open_config_folder() -> Result<(), String> {
+    let config_dir = app_config_dir()
+        .ok_or_else(|| "Could not determine config directory".to_string())?;
+    std::fs::create_dir_all(&config_dir)
+        .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    
+    open_in_file_manager(&config_dir)
+}
+
+#[tauri::command]
+fn get_config_path() -> Result<String, String> {
+    app_config_dir()
+        .map(|d| d.join("settings.json").to_string_lossy().to_string())
+        .ok_or_else(|| "Could not determine config directory".to_string())
+}
+
 #[tauri::command]
 fn auto_detect_paths(app: AppHandle) -> Settings {
     let detected = auto_detect_mc_paths();
@@ -701,25 +1931,72 @@ fn get_pack_display_name(pack_path: &std::path::Path) -> Option<String> {
 
 #[tauri::command]
 fn open_skinmaster(app: AppHandle) -> Result<(), String> {
+    if !cfg!(windows) {
+        return Err("SkinMaster is Windows-only".to_string());
+    }
+
+    let external_path = app.state::<AppState>().settings.read().skinmaster_path.clone();
+    if let Some(external) = external_path.filter(|p| !p.trim().is_empty()) {
+        let external = PathBuf::from(external);
+        if !external.is_file() {
+            return Err(format!("SkinMaster not found at {}. Check the path in Settings.", external.display()));
+        }
+        launch_skinmaster(&external)?;
+        emit_log(&app, "INFO", &format!("Launched SkinMaster from {}", external.display()));
+        return Ok(());
+    }
+
     let temp_dir = std::env::temp_dir().join("Blocksmith");
     std::fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
 
     let skinmaster_path = temp_dir.join("SkinMaster.exe");
+    extract_skinmaster(&skinmaster_path)?;
+    launch_skinmaster(&skinmaster_path)?;
+
+    emit_log(&app, "INFO", "Launched SkinMaster");
+
+    Ok(())
+}
 
-    std::fs::write(&skinmaster_path, SKINMASTER_EXE)
-        .map_err(|e| format!("Failed to extract SkinMaster.exe: {}", e))?;
+const SKINMASTER_BLOCKED_HINT: &str = "SkinMaster.exe was likely blocked or quarantined by antivirus. \
+    Restore it or add an exclusion for the Blocksmith temp folder, or set an external SkinMaster path in Settings.";
 
-    std::process::Command::new(&skinmaster_path)
-        .current_dir(&temp_dir)
-        .spawn()
-        .map_err(|e| format!("Failed to launch SkinMaster: {}", e))?;
+fn skinmaster_size_matches(path: &std::path::Path) -> bool {
+    std::fs::metadata(path).is_ok_and(|m| m.len() == SKINMASTER_EXE.len() as u64)
+}
 
-    emit_log(&app, "INFO", "Launched SkinMaster");
+/// Writes the bundled SkinMaster.exe unless an intact copy is already there,
+/// then checks it survived long enough for antivirus to have had a look.
+fn extract_skinmaster(path: &std::path::Path) -> Result<(), String> {
+    if !skinmaster_size_matches(path) {
+        if let Err(e) = std::fs::write(path, SKINMASTER_EXE) {
+            return Err(if e.kind() == std::io::ErrorKind::PermissionDenied {
+                SKINMASTER_BLOCKED_HINT.to_string()
+            } else {
+                format!("Failed to extract SkinMaster.exe: {}", e)
+            });
+        }
+    }
 
+    if !skinmaster_size_matches(path) {
+        return Err(SKINMASTER_BLOCKED_HINT.to_string());
+    }
     Ok(())
 }
 
+fn launch_skinmaster(path: &std::path::Path) -> Result<(), String> {
+    let mut command = std::process::Command::new(path);
+    if let Some(dir) = path.parent() {
+        command.current_dir(dir);
+    }
+
+    command.spawn().map(|_| ()).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied => SKINMASTER_BLOCKED_HINT.to_string(),
+        _ => format!("Failed to launch SkinMaster: {}", e),
+    })
+}
+
 #[tauri::command]
 fn open_premium_cache() -> Result<(), String> {
     if let Some(roaming) = dirs::config_dir() {
@@ -744,28 +2021,274 @@ fn open_premium_cache() -> Result<(), String> {
 }
 
 
+/// Error returned by operations stopped through cancel_operation.
+const OPERATION_CANCELLED: &str = "cancelled";
+
 fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<(), String> {
+    copy_dir_cancellable(src, dst, &AtomicBool::new(false))
+}
+
+/// copy_dir_recursive that stops with OPERATION_CANCELLED once `cancel` is set.
+fn copy_dir_cancellable(src: &std::path::Path, dst: &std::path::Path, cancel: &AtomicBool) -> Result<(), String> {
     for entry in std::fs::read_dir(src).map_err(|e| e.to_string())? {
+        if cancel.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(OPERATION_CANCELLED.to_string());
+        }
         let entry = entry.map_err(|e| e.to_string())?;
         let src_entry = entry.path();
         let dst_entry = dst.join(entry.file_name());
         
         if src_entry.is_dir() {
-            std::fs::create_dir_all(&dst_entry).map_err(|e| e.to_string())?;
-            copy_dir_recursive(&src_entry, &dst_entry)?;
+            std::fs::create_dir_all(long_path(&dst_entry)?).map_err(|e| e.to_string())?;
+            copy_dir_cancellable(&src_entry, &dst_entry, cancel)?;
         } else {
-            std::fs::copy(&src_entry, &dst_entry).map_err(|e| e.to_string())?;
+            std::fs::copy(long_path(&src_entry)?, long_path(&dst_entry)?).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PremiumBackup {
+    pub premium_pack_path: String,
+    pub backup_path: String,
+    pub created: String,
+}
+
+fn premium_backups_dir() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|d| d.join("blocksmith").join("backups").join("premium_cache"))
+}
+
+fn load_premium_backups() -> Vec<PremiumBackup> {
+    premium_backups_dir()
+        .and_then(|d| std::fs::read_to_string(d.join("backups.json")).ok())
+        .and_then(|c| serde_json::from_str::<Vec<PremiumBackup>>(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_premium_backups(backups: &[PremiumBackup]) -> Result<(), String> {
+    let dir = premium_backups_dir()
+        .ok_or_else(|| "Could not determine config directory".to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(backups).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join("backups.json"), content).map_err(|e| e.to_string())
+}
+
+/// Backups kept per premium pack; older ones are deleted as new ones are taken.
+const PREMIUM_BACKUPS_KEPT: usize = 3;
+
+/// Copies a premium cache pack into a timestamped backup folder and records it
+/// so `undo_4d_import` can restore it later. A cancelled backup is removed.
+fn backup_premium_pack(premium_path: &std::path::Path, cancel: &AtomicBool) -> Result<std::path::PathBuf, String> {
+    let dir = premium_backups_dir()
+        .ok_or_else(|| "Could not determine config directory".to_string())?;
+    let folder_name = premium_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Unknown");
+    let now = chrono::Local::now();
+    let stamp = format!("{} {}", folder_name, now.format("%Y%m%d-%H%M%S"));
+    // Two imports in the same second would otherwise share a folder
+    let mut backup_path = dir.join(&stamp);
+    let mut suffix = 2;
+    while backup_path.exists() {
+        backup_path = dir.join(format!("{} ({})", stamp, suffix));
+        suffix += 1;
+    }
+    
+    std::fs::create_dir_all(&backup_path)
+        .map_err(|e| format!("Failed to create backup folder: {}", e))?;
+    match copy_dir_cancellable(premium_path, &backup_path, cancel) {
+        Ok(()) => {}
+        Err(e) if e == OPERATION_CANCELLED => {
+            let _ = std::fs::remove_dir_all(&backup_path);
+            return Err(e);
+        }
+        Err(e) => return Err(format!("Failed to back up premium pack: {}", e)),
+    }
+    
+    let mut backups = load_premium_backups();
+    backups.push(PremiumBackup {
+        premium_pack_path: premium_path.to_string_lossy().to_string(),
+        backup_path: backup_path.to_string_lossy().to_string(),
+        created: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+    });
+    prune_premium_backups(&mut backups, &premium_path.to_string_lossy());
+    save_premium_backups(&backups)?;
+    
+    Ok(backup_path)
+}
+
+/// Drops all but the newest PREMIUM_BACKUPS_KEPT backups of `premium_pack_path`,
+/// deleting their folders.
+fn prune_premium_backups(backups: &mut Vec<PremiumBackup>, premium_pack_path: &str) {
+    let count = backups.iter().filter(|b| b.premium_pack_path == premium_pack_path).count();
+    let mut excess = count.saturating_sub(PREMIUM_BACKUPS_KEPT);
+    backups.retain(|b| {
+        if excess == 0 || b.premium_pack_path != premium_pack_path {
+            return true;
         }
+        excess -= 1;
+        let _ = std::fs::remove_dir_all(&b.backup_path);
+        false
+    });
+}
+
+#[tauri::command]
+fn undo_4d_import(premium_pack_path: String, app: AppHandle) -> Result<(), String> {
+    ensure_not_safe_mode(&app)?;
+    let premium_path = std::path::Path::new(&premium_pack_path);
+    
+    let allowed_base = if let Some(roaming) = dirs::config_dir() {
+        roaming.join("Minecraft Bedrock").join("premium_cache").join("skin_packs")
+    } else {
+        return Err("Could not determine AppData directory".to_string());
+    };
+    if !premium_path.starts_with(&allowed_base) {
+        return Err("premium_pack_path is outside the premium cache skin_packs directory".to_string());
+    }
+    
+    let mut backups = load_premium_backups();
+    let index = backups
+        .iter()
+        .rposition(|b| b.premium_pack_path == premium_pack_path)
+        .ok_or_else(|| "No backup found for this premium pack".to_string())?;
+    let backup = backups[index].clone();
+    let backup_path = std::path::Path::new(&backup.backup_path);
+    
+    if !backup_path.exists() {
+        backups.remove(index);
+        save_premium_backups(&backups)?;
+        return Err(format!("Backup folder no longer exists: {}", backup.backup_path));
+    }
+    
+    emit_log(&app, "INFO", &format!("Restoring '{}' from backup taken {}", premium_pack_path, backup.created));
+    
+    if premium_path.exists() {
+        std::fs::remove_dir_all(premium_path)
+            .map_err(|e| format!("Failed to clear premium pack folder: {}", e))?;
     }
+    std::fs::create_dir_all(premium_path)
+        .map_err(|e| format!("Failed to recreate premium pack folder: {}", e))?;
+    copy_dir_recursive(backup_path, premium_path)
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+    
+    let _ = std::fs::remove_dir_all(backup_path);
+    backups.remove(index);
+    save_premium_backups(&backups)?;
+    
+    emit_log(&app, "SUCCESS", "Premium pack restored. Restart Minecraft to see the changes.");
+    
     Ok(())
 }
 
+/// Every skin SkinMaster would see in a 4D skin pack, with its geometry.
+#[tauri::command]
+fn list_4d_skins(skin_pack_path: String) -> Result<Vec<modules::skin_inventory::SkinEntry>, String> {
+    let skin_path = std::path::Path::new(&skin_pack_path);
+    if !skin_path.is_dir() {
+        return Err("4D skin pack folder does not exist".to_string());
+    }
+
+    modules::skin_inventory::list_4d_skins(skin_path)
+}
+
+#[tauri::command]
+fn validate_4d_skin(skin_pack_path: String) -> Result<Validation4D, String> {
+    let skin_path = std::path::Path::new(&skin_pack_path);
+    if !skin_path.exists() || !skin_path.is_dir() {
+        return Err("4D skin pack folder does not exist".to_string());
+    }
+    
+    Ok(validate_4d_folder(skin_path))
+}
+
+/// How Blocksmith would classify a pack file, without a full scan. Multi-pack
+/// archives return one type per pack.
+#[tauri::command]
+async fn classify_file(path: String) -> Result<Vec<PackType>, String> {
+    tokio::task::spawn_blocking(move || classify_archive(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| format!("Classification failed: {}", e))?
+}
+
+#[tauri::command]
+async fn verify_archive(path: String) -> Result<ArchiveHealth, String> {
+    tokio::task::spawn_blocking(move || verify_archive_entries(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| format!("Verification failed: {}", e))?
+}
+
+#[tauri::command]
+async fn pack_compression_info(path: String) -> Result<CompressionInfo, String> {
+    tokio::task::spawn_blocking(move || compression_info(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| format!("Compression check failed: {}", e))?
+}
+
+/// Strictly parses every .json file in a pack archive or folder and reports
+/// the ones that fail, e.g. a trailing comma in a hand-edited recipe.
+#[tauri::command]
+async fn validate_pack_json(path: String) -> Result<Vec<JsonError>, String> {
+    tokio::task::spawn_blocking(move || {
+        use rayon::prelude::*;
+        let pack_path = std::path::Path::new(&path);
+        let mut errors: Vec<JsonError> = if pack_path.is_dir() {
+            collect_relative_files(pack_path)
+                .into_par_iter()
+                .filter(|rel| rel.to_lowercase().ends_with(".json"))
+                .filter_map(|rel| {
+                    let bytes = std::fs::read(pack_path.join(&rel)).ok()?;
+                    json_error(&rel, &bytes)
+                })
+                .collect()
+        } else {
+            read_archive_json_files(pack_path)?
+                .into_par_iter()
+                .filter_map(|(rel, bytes)| json_error(&rel, &bytes))
+                .collect()
+        };
+        errors.sort_by(|a, b| a.file.cmp(&b.file));
+        Ok(errors)
+    })
+    .await
+    .map_err(|e| format!("JSON validation failed: {}", e))?
+}
+
+/// Files in a pack archive or folder larger than `threshold_mb` uncompressed,
+/// biggest first, e.g. a stray PSD or video bundled by mistake.
+#[tauri::command]
+async fn find_bloated_entries(path: String, threshold_mb: u64) -> Result<Vec<(String, u64)>, String> {
+    let threshold = threshold_mb.saturating_mul(1024 * 1024);
+    tokio::task::spawn_blocking(move || {
+        let mut entries: Vec<(String, u64)> = list_pack_files(std::path::Path::new(&path))?
+            .into_iter()
+            .filter(|(_, size)| *size > threshold)
+            .collect();
+        entries.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        Ok(entries)
+    })
+    .await
+    .map_err(|e| format!("Size check failed: {}", e))?
+}
+
 #[tauri::command]
+async fn diff_packs(path_a: String, path_b: String) -> Result<PackDiff, String> {
+    tokio::task::spawn_blocking(move || {
+        diff_pack_paths(std::path::Path::new(&path_a), std::path::Path::new(&path_b))
+    })
+    .await
+    .map_err(|e| format!("Diff failed: {}", e))?
+}
+
+// Runs off the main thread so cancel_operation can be handled mid-copy
+#[tauri::command(async)]
 fn import_4d_skin_to_premium(
     skin_pack_path: String,
     premium_pack_path: String,
     app: AppHandle,
 ) -> Result<(), String> {
+    ensure_not_safe_mode(&app)?;
     emit_log(&app, "INFO", &format!("Importing 4D skin from '{}' to '{}'", skin_pack_path, premium_pack_path));
     
     let skin_path = std::path::Path::new(&skin_pack_path);
@@ -788,53 +2311,358 @@ fn import_4d_skin_to_premium(
         return Err("Premium pack folder does not exist".to_string());
     }
     
+    let state = app.state::<AppState>();
+    let cancel = &state.cancel_requested;
+    cancel.store(false, std::sync::atomic::Ordering::Relaxed);
+    
+    let backup_path = backup_premium_pack(premium_path, cancel)?;
+    emit_log(&app, "INFO", &format!("Backed up premium pack to '{}'", backup_path.display()));
+    
+    let result = copy_4d_into_premium(skin_path, premium_path, cancel, &app);
+    if result.as_ref().is_err_and(|e| e == OPERATION_CANCELLED) {
+        emit_log(&app, "WARN", "4D import cancelled - restoring premium pack from backup");
+        undo_4d_import(premium_pack_path, app.clone())?;
+    }
+    result?;
+    
+    emit_log(&app, "SUCCESS", "4D skin pack imported successfully! Restart Minecraft to see the changes.");
+    
+    Ok(())
+}
+
+fn copy_4d_into_premium(
+    skin_path: &std::path::Path,
+    premium_path: &std::path::Path,
+    cancel: &AtomicBool,
+    app: &AppHandle,
+) -> Result<(), String> {
     let texts_folder = premium_path.join("texts");
     if texts_folder.exists() {
         std::fs::remove_dir_all(&texts_folder)
             .map_err(|e| format!("Failed to remove texts folder: {}", e))?;
-        emit_log(&app, "INFO", "Removed existing texts folder");
+        emit_log(app, "INFO", "Removed existing texts folder");
     }
     
-    for entry in std::fs::read_dir(skin_path).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
+    // Top-level folders are replaced wholesale, so clear them before the
+    // parallel copy starts writing into them
+    for entry in std::fs::read_dir(skin_path).map_err(|e| e.to_string())?.flatten() {
         let src_path = entry.path();
-        let file_name = entry.file_name();
-        let dst_path = premium_path.join(&file_name);
-        
-        if file_name == "manifest.json" {
-            emit_log(&app, "INFO", "Skipping manifest.json (keeping premium pack's manifest)");
+        if !src_path.is_dir() {
             continue;
         }
-        
-        if src_path.is_dir() {
-            if dst_path.exists() {
-                std::fs::remove_dir_all(&dst_path)
-                    .map_err(|e| format!("Failed to remove existing folder: {}", e))?;
+        let dst_path = premium_path.join(entry.file_name());
+        if dst_path.exists() {
+            std::fs::remove_dir_all(&dst_path)
+                .map_err(|e| format!("Failed to remove existing folder: {}", e))?;
+        }
+        std::fs::create_dir_all(&dst_path)
+            .map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+
+    let mut files = collect_relative_files(skin_path);
+    if let Some(pos) = files.iter().position(|rel| rel == "manifest.json") {
+        files.remove(pos);
+        emit_log(app, "INFO", "Skipping manifest.json (keeping premium pack's manifest)");
+    }
+    emit_log(app, "INFO", &format!("Copying {} files", files.len()));
+
+    let total = files.len();
+    let progress = BatchProgress::new(app, total, 50, "Copied", "importing");
+    {
+        use rayon::prelude::*;
+        files.par_iter().try_for_each(|rel| {
+            if cancel.load(std::sync::atomic::Ordering::Relaxed) {
+                return Err(OPERATION_CANCELLED.to_string());
             }
-            std::fs::create_dir_all(&dst_path)
-                .map_err(|e| format!("Failed to create folder: {}", e))?;
-            
-            copy_dir_recursive(&src_path, &dst_path)?;
-            emit_log(&app, "INFO", &format!("Copied folder: {:?}", file_name));
-        } else {
-            std::fs::copy(&src_path, &dst_path)
-                .map_err(|e| format!("Failed to copy file: {}", e))?;
-            emit_log(&app, "INFO", &format!("Copied file: {:?}", file_name));
+            let dst_path = long_path(&premium_path.join(rel))?;
+            if let Some(parent) = dst_path.parent() {
+                // create_dir_all tolerates another thread creating the same folder
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create folder: {}", e))?;
+            }
+            std::fs::copy(long_path(&skin_path.join(rel))?, &dst_path)
+                .map_err(|e| format!("Failed to copy {}: {}", rel, e))?;
+            progress.tick();
+            Ok(())
+        })?;
+    }
+    emit_log(app, "INFO", &format!("Copied {} files", total));
+    
+    Ok(())
+}
+
+/// Asks the running 4D import or premium backup to stop. The import then
+/// restores the premium pack from its backup and fails with "cancelled".
+#[tauri::command]
+fn cancel_operation(app: AppHandle) {
+    app.state::<AppState>().cancel_requested.store(true, std::sync::atomic::Ordering::Relaxed);
+    emit_log(&app, "INFO", "Cancellation requested");
+}
+
+/// Extracts a 4D skin pack archive to a temp folder and imports it into a
+/// premium cache pack in one go, instead of process_pack + import_4d_skin_to_premium.
+#[tauri::command]
+async fn install_4d_to_premium(source_pack: String, premium_pack_path: String, app: AppHandle) -> Result<(), String> {
+    ensure_not_safe_mode(&app)?;
+    let source = PathBuf::from(&source_pack);
+    if !source.is_file() {
+        return Err(format!("Source pack does not exist: {}", source_pack));
+    }
+
+    let emit_step = |current: usize, message: &str| {
+        let _ = app.emit("progress", serde_json::json!({
+            "current": current,
+            "total": 3,
+            "message": message
+        }));
+    };
+
+    emit_step(0, "Extracting 4D skin pack...");
+    let temp_dir = std::env::temp_dir()
+        .join("Blocksmith")
+        .join(format!("4d-import-{}", uuid::Uuid::new_v4()));
+    let temp_for_extract = temp_dir.clone();
+    let extracted = tokio::task::spawn_blocking(move || {
+        std::fs::create_dir_all(&temp_for_extract)
+            .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+        let dest = extract_pack_to_destination(&source, &temp_for_extract, PackType::SkinPack4D, None, Some("pack"))?;
+        let dest = PathBuf::from(dest);
+        // Many 4D packs wrap the skin pack in an extra folder
+        let files = collect_relative_files(&dest);
+        Ok::<PathBuf, String>(
+            find_skins_json(&dest, &files)
+                .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+                .unwrap_or(dest),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let result = match extracted {
+        Ok(skin_dir) => {
+            emit_step(1, "Importing into premium cache...");
+            let app_for_import = app.clone();
+            tokio::task::spawn_blocking(move || {
+                import_4d_skin_to_premium(skin_dir.to_string_lossy().to_string(), premium_pack_path, app_for_import)
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r)
+        }
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = std::fs::remove_dir_all(&temp_dir) {
+        emit_log(&app, "WARN", &format!("Failed to clean up '{}': {}", temp_dir.display(), e));
+    }
+
+    result?;
+    emit_step(3, "Done - restart Minecraft to see the new skins");
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Extracted4DPack {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub size_formatted: String,
+    /// The .mcpack it came from, when it's still around
+    pub source: Option<String>,
+}
+
+fn extracted_4d_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    get_destination_for_pack_type(PackType::SkinPack4D, app.clone())
+        .map(PathBuf::from)
+        .ok_or_else(|| "No 4D skin pack folder or scan location configured".to_string())
+}
+
+/// Extracted 4D skin packs, with the source archive each came from if it's
+/// still in the scan folder. Ones without a source are usually leftovers
+/// from packs already imported into the premium cache.
+#[tauri::command]
+async fn list_4d_extractions(app: AppHandle) -> Result<Vec<Extracted4DPack>, String> {
+    let dir = extracted_4d_dir(&app)?;
+    let scan_location = app.state::<AppState>().settings.read().scan_location.clone();
+
+    tokio::task::spawn_blocking(move || {
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let sources: std::collections::HashMap<String, PathBuf> = scan_location
+            .map(|scan| collect_pack_files(std::path::Path::new(&scan), 0, &[], false).unwrap_or_default())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|file| {
+                let stem = file.file_stem()?.to_string_lossy().to_string();
+                Some((sanitize_folder_name(&stem).to_lowercase(), file))
+            })
+            .collect();
+
+        let mut packs: Vec<Extracted4DPack> = std::fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .map(|path| {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let source = read_receipt(&path)
+                    .map(|r| PathBuf::from(r.source))
+                    .filter(|source| source.is_file())
+                    .or_else(|| sources.get(&name.to_lowercase()).cloned());
+                let size = calculate_folder_size(&path);
+                Extracted4DPack {
+                    name,
+                    path: path.to_string_lossy().to_string(),
+                    size,
+                    size_formatted: format_bytes(size),
+                    source: source.map(|s| s.to_string_lossy().to_string()),
+                }
+            })
+            .collect();
+
+        packs.sort_by_key(|p| p.name.to_lowercase());
+        Ok(packs)
+    })
+    .await
+    .map_err(|e| format!("Listing 4D extractions failed: {}", e))?
+}
+
+/// Deletes extracted 4D packs listed by list_4d_extractions. Only folders
+/// directly inside the 4D skin pack folder are accepted.
+#[tauri::command]
+fn remove_4d_extractions(paths: Vec<String>, app: AppHandle) -> Result<Vec<String>, String> {
+    ensure_not_safe_mode(&app)?;
+    let dir = extracted_4d_dir(&app)?;
+    let dir = dir.canonicalize().unwrap_or(dir);
+    let mut removed = Vec::new();
+    let mut errors = Vec::new();
+
+    for path in paths {
+        let folder = std::path::Path::new(&path);
+        let inside = folder
+            .canonicalize()
+            .ok()
+            .and_then(|c| c.parent().map(|p| p == dir))
+            .unwrap_or(false);
+        if !inside {
+            errors.push(format!("{}: not an extracted 4D skin pack", path));
+            continue;
+        }
+        match std::fs::remove_dir_all(folder) {
+            Ok(()) => {
+                emit_log(&app, "INFO", &format!("Removed extracted 4D pack: {}", path));
+                removed.push(path);
+            }
+            Err(e) => errors.push(format!("{}: {}", path, e)),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(format!("Some deletions failed: {}", errors.join("; ")));
+    }
+    Ok(removed)
+}
+
+const PREMIUM_CACHE_WATCHER: &str = "premium_cache";
+const SCAN_FOLDER_WATCHER: &str = "scan_folder";
+
+struct RunningWatcher {
+    id: uuid::Uuid,
+    path: String,
+    stop_tx: std::sync::mpsc::SyncSender<()>,
+}
+
+/// Held by a watcher thread; unregisters the watcher however the thread
+/// exits, so a watcher that dies never leaves a stuck "Already watching".
+struct WatcherRegistration {
+    app: AppHandle,
+    name: &'static str,
+    id: uuid::Uuid,
+}
+
+impl Drop for WatcherRegistration {
+    fn drop(&mut self) {
+        let state = self.app.state::<AppState>();
+        let mut watchers = state.watchers.lock();
+        // A newer watcher may already have taken the name after stop_watching
+        if watchers.get(self.name).is_some_and(|w| w.id == self.id) {
+            watchers.remove(self.name);
         }
     }
-    
-    emit_log(&app, "SUCCESS", "4D skin pack imported successfully! Restart Minecraft to see the changes.");
-    
-    Ok(())
+}
+
+/// Registers a named watcher on `path`. Returns the channel that stops it and
+/// the registration the watcher thread keeps for as long as it runs.
+fn register_watcher(
+    app: &AppHandle,
+    name: &'static str,
+    path: &std::path::Path,
+) -> Result<(std::sync::mpsc::Receiver<()>, WatcherRegistration), String> {
+    let state = app.state::<AppState>();
+    let mut watchers = state.watchers.lock();
+    if watchers.contains_key(name) {
+        return Err("Already watching".to_string());
+    }
+    // Buffered so stop_watching never waits on a watcher mid-poll
+    let (stop_tx, stop_rx) = std::sync::mpsc::sync_channel::<()>(1);
+    let id = uuid::Uuid::new_v4();
+    watchers.insert(name, RunningWatcher { id, path: path.to_string_lossy().to_string(), stop_tx });
+    Ok((stop_rx, WatcherRegistration { app: app.clone(), name, id }))
+}
+
+/// How often a watcher checks that the folder it watches still exists.
+const WATCH_ROOT_POLL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Tells the frontend a watcher ended on its own. The thread returning then
+/// drops its WatcherRegistration, which clears the "already watching" state.
+fn report_watcher_stopped(app: &AppHandle, name: &str, reason: &str) {
+    emit_log(app, "WARN", &format!("Stopped watching {}: {}", watcher_label(name), reason));
+    let _ = app.emit("watcher-stopped", serde_json::json!({
+        "watcher": name,
+        "reason": reason
+    }));
+}
+
+fn watcher_label(name: &str) -> &'static str {
+    if name == PREMIUM_CACHE_WATCHER { "premium cache" } else { "scan folder" }
+}
+
+fn count_watcher_event(app: &AppHandle) {
+    app.state::<AppState>().watcher_events.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedFolder {
+    pub watcher: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherStatus {
+    pub active: bool,
+    pub watched: Vec<WatchedFolder>,
+    pub events_emitted: u64,
+}
+
+#[tauri::command]
+fn watcher_status(app: AppHandle) -> WatcherStatus {
+    let state = app.state::<AppState>();
+    let mut watched: Vec<WatchedFolder> = state.watchers.lock()
+        .iter()
+        .map(|(name, w)| WatchedFolder { watcher: name.to_string(), path: w.path.clone() })
+        .collect();
+    watched.sort_by(|a, b| a.watcher.cmp(&b.watcher));
+    WatcherStatus {
+        active: !watched.is_empty(),
+        watched,
+        events_emitted: state.watcher_events.load(std::sync::atomic::Ordering::Relaxed),
+    }
 }
 
 #[tauri::command]
 fn watch_premium_cache(app: AppHandle) -> Result<(), String> {
-    let watching = app.state::<AppState>().watching.load(Ordering::SeqCst);
-    if watching {
-        return Err("Already watching".to_string());
-    }
-    
     let premium_cache = if let Some(roaming) = dirs::config_dir() {
         roaming.join("Minecraft Bedrock").join("premium_cache")
     } else {
@@ -845,17 +2673,21 @@ fn watch_premium_cache(app: AppHandle) -> Result<(), String> {
         return Err("Premium cache folder not found".to_string());
     }
     
-    app.state::<AppState>().watching.store(true, Ordering::SeqCst);
-    
-    let (stop_tx, stop_rx) = std::sync::mpsc::sync_channel::<()>(0);
-    *app.state::<AppState>().watch_stop_tx.lock() = Some(stop_tx);
+    let (stop_rx, registration) = register_watcher(&app, PREMIUM_CACHE_WATCHER, &premium_cache)?;
 
     let app_clone = app.clone();
+    let root_removed = Arc::new(AtomicBool::new(false));
+    let root_removed_for_events = root_removed.clone();
+    let root_for_events = premium_cache.clone();
     
     std::thread::spawn(move || {
+        let _registration = registration;
         let mut watcher: notify::RecommendedWatcher = match Watcher::new(
             move |res: Result<Event, notify::Error>| {
                 if let Ok(event) = res {
+                    if matches!(event.kind, EventKind::Remove(_)) && event.paths.iter().any(|p| p == &root_for_events) {
+                        root_removed_for_events.store(true, std::sync::atomic::Ordering::Relaxed);
+                    }
                     let timestamp = chrono::Local::now().format("%H:%M:%S%.3f").to_string();
                     
                     let event_type = match event.kind {
@@ -887,6 +2719,7 @@ fn watch_premium_cache(app: AppHandle) -> Result<(), String> {
                         };
                         
                         let _ = app_clone.emit("watcher-event", watcher_event);
+                        count_watcher_event(&app_clone);
                     }
                 }
             },
@@ -906,19 +2739,143 @@ fn watch_premium_cache(app: AppHandle) -> Result<(), String> {
         
         emit_log(&app, "INFO", &format!("Watching: {}", premium_cache.display()));
         
-        let _ = stop_rx.recv();
+        // notify goes quiet once the watched folder is gone, so check for that
+        // alongside waiting for stop_watching
+        while let Err(std::sync::mpsc::RecvTimeoutError::Timeout) = stop_rx.recv_timeout(WATCH_ROOT_POLL) {
+            if root_removed.load(std::sync::atomic::Ordering::Relaxed) || !premium_cache.exists() {
+                report_watcher_stopped(&app, PREMIUM_CACHE_WATCHER, "the folder was deleted");
+                break;
+            }
+        }
     });
     
     Ok(())
 }
 
+const SCAN_WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_secs(2);
+const SCAN_WATCH_POLL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Watches the scan location for new pack files and emits "new-pack-detected"
+/// with each pack found. A file is only scanned once its size has held still
+/// for SCAN_WATCH_DEBOUNCE, so downloads still in progress are skipped.
+#[tauri::command]
+fn watch_scan_folder(app: AppHandle) -> Result<(), String> {
+    let scan_location = app.state::<AppState>().settings.read().scan_location.clone()
+        .ok_or_else(|| "No scan location configured".to_string())?;
+    let scan_dir = PathBuf::from(&scan_location);
+    if !scan_dir.is_dir() {
+        return Err(format!("Scan location not found: {}", scan_location));
+    }
+
+    let (stop_rx, registration) = register_watcher(&app, SCAN_FOLDER_WATCHER, &scan_dir)?;
+
+    // Pack file -> (last change seen, size at that time)
+    let pending: Arc<parking_lot::Mutex<std::collections::HashMap<PathBuf, (std::time::Instant, u64)>>> =
+        Arc::new(parking_lot::Mutex::new(std::collections::HashMap::new()));
+    let pending_for_events = pending.clone();
+
+    std::thread::spawn(move || {
+        let _registration = registration;
+        let mut watcher: notify::RecommendedWatcher = match Watcher::new(
+            move |res: Result<Event, notify::Error>| {
+                let Ok(event) = res else { return };
+                if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    return;
+                }
+                for path in event.paths {
+                    let is_pack = path.extension()
+                        .and_then(|e| e.to_str())
+                        .is_some_and(|ext| ["mcpack", "mcaddon", "mctemplate"].contains(&ext.to_lowercase().as_str()));
+                    if is_pack {
+                        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                        pending_for_events.lock().insert(path, (std::time::Instant::now(), size));
+                    }
+                }
+            },
+            notify::Config::default(),
+        ) {
+            Ok(w) => w,
+            Err(e) => {
+                emit_log(&app, "ERROR", &format!("Failed to create watcher: {}", e));
+                return;
+            }
+        };
+
+        if let Err(e) = watcher.watch(&scan_dir, RecursiveMode::NonRecursive) {
+            emit_log(&app, "ERROR", &format!("Failed to watch: {}", e));
+            return;
+        }
+
+        emit_log(&app, "INFO", &format!("Watching for new packs: {}", scan_dir.display()));
+
+        while let Err(std::sync::mpsc::RecvTimeoutError::Timeout) = stop_rx.recv_timeout(SCAN_WATCH_POLL) {
+            if !scan_dir.is_dir() {
+                report_watcher_stopped(&app, SCAN_FOLDER_WATCHER, "the folder was deleted");
+                break;
+            }
+            let ready: Vec<(PathBuf, u64)> = {
+                let mut pending = pending.lock();
+                let mut ready = Vec::new();
+                pending.retain(|path, (seen, size)| {
+                    if seen.elapsed() < SCAN_WATCH_DEBOUNCE {
+                        return true;
+                    }
+                    match std::fs::metadata(path).map(|m| m.len()) {
+                        Ok(current) if current == *size && current > 0 => {
+                            ready.push((path.clone(), current));
+                            false
+                        }
+                        Ok(current) => {
+                            *seen = std::time::Instant::now();
+                            *size = current;
+                            true
+                        }
+                        // Removed or renamed before it settled
+                        Err(_) => false,
+                    }
+                });
+                ready
+            };
+
+            for (path, size) in ready {
+                let icon_names = app.state::<AppState>().settings.read().icon_names();
+                let packs = scan_single_pack(&path, &icon_names);
+                if packs.is_empty() {
+                    emit_log(&app, "WARN", &format!("No packs found in new file {}", path.display()));
+                    continue;
+                }
+                emit_log(&app, "INFO", &format!("New download detected: {} ({} packs)", path.display(), packs.len()));
+                for mut pack in packs {
+                    if pack.folder_size.is_none() {
+                        pack.folder_size = Some(size);
+                        pack.folder_size_formatted = Some(format_bytes(size));
+                    }
+                    let _ = app.emit("new-pack-detected", pack);
+                    count_watcher_event(&app);
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Stops the named watcher ("premium_cache" or "scan_folder"), or every
+/// running watcher when no name is given.
 #[tauri::command]
-fn stop_watching(app: AppHandle) -> Result<(), String> {
-    app.state::<AppState>().watching.store(false, Ordering::SeqCst);
-    if let Some(tx) = app.state::<AppState>().watch_stop_tx.lock().take() {
-        let _ = tx.send(());
+fn stop_watching(watcher: Option<String>, app: AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let stopped: Vec<(&'static str, RunningWatcher)> = {
+        let mut watchers = state.watchers.lock();
+        match watcher.as_deref() {
+            Some(name) => watchers.remove_entry(name).into_iter().collect(),
+            None => watchers.drain().collect(),
+        }
+    };
+    for (name, running) in stopped {
+        let _ = running.stop_tx.try_send(());
+        emit_log(&app, "INFO", &format!("Stopped watching {}", watcher_label(name)));
     }
-    emit_log(&app, "INFO", "Stopped watching premium cache");
     Ok(())
 }
 
@@ -930,6 +2887,113 @@ pub struct PackStats {
     pub total_size_formatted: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttentionCategory {
+    pub kind: String,
+    pub count: usize,
+    pub pack_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttentionSummary {
+    pub total: usize,
+    pub categories: Vec<AttentionCategory>,
+}
+
+const ATTENTION_KINDS: &[&str] = &[
+    "encrypted",
+    "missing_manifest",
+    "4d_special_files",
+    "dependency_missing",
+    "version_incompatible",
+    "type_mismatch",
+    "legacy_format",
+    "missing_textures",
+    "guessed_type",
+    "macos_junk",
+    "other",
+];
+
+fn attention_kind_for_reason(reason: AttentionReason) -> &'static str {
+    match reason {
+        AttentionReason::Encrypted => "encrypted",
+        AttentionReason::MissingManifest => "missing_manifest",
+        AttentionReason::MultipleGeometryFolders | AttentionReason::ContainsReadme => "4d_special_files",
+        AttentionReason::DependencyMissing => "dependency_missing",
+        AttentionReason::VersionIncompatible => "version_incompatible",
+        AttentionReason::TypeMismatch => "type_mismatch",
+        AttentionReason::LegacyFormat => "legacy_format",
+        AttentionReason::MissingTextures => "missing_textures",
+        AttentionReason::GuessedType => "guessed_type",
+        AttentionReason::MacJunkFiles => "macos_junk",
+    }
+}
+
+fn attention_kinds_for_message(message: &str) -> Vec<&'static str> {
+    let lower = message.to_lowercase();
+    let mut kinds = Vec::new();
+    if lower.contains("encrypt") || lower.contains("password") {
+        kinds.push("encrypted");
+    }
+    if lower.contains("manifest") && (lower.contains("missing") || lower.contains("no manifest")) {
+        kinds.push("missing_manifest");
+    }
+    if lower.contains("skinmaster") || lower.contains("geometry") || lower.contains("readme") {
+        kinds.push("4d_special_files");
+    }
+    if lower.contains("dependenc") {
+        kinds.push("dependency_missing");
+    }
+    if lower.contains("incompatible") || lower.contains("format_version") || lower.contains("min_engine_version") {
+        kinds.push("version_incompatible");
+    }
+    if lower.contains("missing textures") {
+        kinds.push("missing_textures");
+    }
+    if kinds.is_empty() {
+        kinds.push("other");
+    }
+    kinds
+}
+
+fn summarize_attention(packs: &[PackInfo]) -> AttentionSummary {
+    let mut by_kind: std::collections::HashMap<&'static str, Vec<String>> = std::collections::HashMap::new();
+    let mut total = 0;
+
+    for pack in packs.iter().filter(|p| p.needs_attention.unwrap_or(false)) {
+        total += 1;
+        // Older results carry only the free-form message, so fall back to matching it
+        let mut kinds: Vec<&'static str> = if pack.attention_reasons.is_empty() {
+            attention_kinds_for_message(pack.attention_message.as_deref().unwrap_or(""))
+        } else {
+            pack.attention_reasons.iter().map(|r| attention_kind_for_reason(*r)).collect()
+        };
+        kinds.sort();
+        kinds.dedup();
+        for kind in kinds {
+            by_kind.entry(kind).or_default().push(pack.name.clone());
+        }
+    }
+
+    let categories = ATTENTION_KINDS
+        .iter()
+        .filter_map(|kind| {
+            by_kind.remove(kind).map(|pack_names| AttentionCategory {
+                kind: kind.to_string(),
+                count: pack_names.len(),
+                pack_names,
+            })
+        })
+        .collect();
+
+    AttentionSummary { total, categories }
+}
+
+#[tauri::command]
+fn attention_summary(packs: Vec<PackInfo>) -> AttentionSummary {
+    summarize_attention(&packs)
+}
+
 /// Returns true if a folder name (any casing) contains a mash-up keyword.
 fn folder_name_is_mashup(name: &str) -> bool {
     let lower = name.to_lowercase();
@@ -1113,6 +3177,154 @@ fn launch_minecraft(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Process names of Bedrock builds: the UWP/GDK Windows client and the
+/// community launcher used on Linux and macOS.
+const MINECRAFT_PROCESS_NAMES: &[&str] = &["minecraft.windows.exe", "minecraft.exe", "mcpelauncher-client"];
+
+fn minecraft_process_running() -> bool {
+    #[cfg(target_os = "windows")]
+    let output = {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        std::process::Command::new("tasklist")
+            .args(["/FO", "CSV", "/NH"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+    };
+
+    // comm= is cut to 15 characters on Linux, which mcpelauncher-client exceeds
+    #[cfg(not(target_os = "windows"))]
+    let output = std::process::Command::new("ps")
+        .args(["-A", "-o", "args="])
+        .output();
+
+    // Treat a failed enumeration as "not running" so callers never block on it
+    let Ok(output) = output else {
+        return false;
+    };
+    let listing = String::from_utf8_lossy(&output.stdout).to_lowercase();
+    listing.lines().any(|line| {
+        let name = line.split(',').next().unwrap_or(line).trim().trim_matches('"');
+        let name = name.split_whitespace().next().unwrap_or(name);
+        let name = name.rsplit('/').next().unwrap_or(name);
+        MINECRAFT_PROCESS_NAMES.contains(&name)
+    })
+}
+
+fn warn_if_minecraft_running(app: &AppHandle) {
+    if minecraft_process_running() {
+        emit_log(app, "WARN", "Minecraft is running - close it first if files fail with access denied errors");
+    }
+}
+
+/// Runs warn_if_minecraft_running off the async runtime, since listing
+/// processes can take a while.
+async fn warn_if_minecraft_running_async(app: &AppHandle) {
+    let app = app.clone();
+    let _ = tokio::task::spawn_blocking(move || warn_if_minecraft_running(&app)).await;
+}
+
+#[tauri::command]
+async fn is_minecraft_running() -> bool {
+    tokio::task::spawn_blocking(minecraft_process_running)
+        .await
+        .unwrap_or(false)
+}
+
+const SAFE_MODE_ERROR: &str = "Safe mode enabled — destructive operations are disabled";
+
+fn ensure_not_safe_mode(app: &AppHandle) -> Result<(), String> {
+    if app.state::<AppState>().settings.read().safe_mode.unwrap_or(false) {
+        return Err(SAFE_MODE_ERROR.to_string());
+    }
+    Ok(())
+}
+
+/// Turns safe mode on or off. Turning it off needs `confirm` so a stray call
+/// from the UI can't unlock deletes on a shared machine.
+#[tauri::command]
+fn set_safe_mode(enabled: bool, confirm: Option<bool>, app: AppHandle) -> Result<(), String> {
+    if !enabled && !confirm.unwrap_or(false) {
+        return Err("Disabling safe mode requires confirmation".to_string());
+    }
+    let state = app.state::<AppState>();
+    let mut settings = state.settings.read().clone();
+    settings.safe_mode = Some(enabled);
+    *state.settings.write() = settings.clone();
+    emit_log(&app, "INFO", if enabled { "Safe mode enabled" } else { "Safe mode disabled" });
+    save_settings_to_file(&settings)
+}
+
+/// Fills in `tags` on each pack from `Settings::pack_tags`.
+fn apply_pack_tags(app: &AppHandle, packs: &mut [PackInfo]) {
+    let state = app.state::<AppState>();
+    let settings = state.settings.read();
+    if settings.pack_tags.is_empty() {
+        return;
+    }
+    for pack in packs.iter_mut() {
+        if let Some(tags) = pack.uuid.as_ref().and_then(|u| settings.pack_tags.get(&u.to_lowercase())) {
+            pack.tags = tags.clone();
+        }
+    }
+}
+
+/// Applies `change` to the tag list for `uuid`, drops the entry once it's
+/// empty, and saves. Returns the updated list.
+fn update_pack_tags(app: &AppHandle, uuid: &str, change: impl FnOnce(&mut Vec<String>)) -> Result<Vec<String>, String> {
+    let key = uuid.trim().to_lowercase();
+    if key.is_empty() {
+        return Err("Pack has no UUID".to_string());
+    }
+    let state = app.state::<AppState>();
+    let mut settings = state.settings.read().clone();
+    let mut tags = settings.pack_tags.remove(&key).unwrap_or_default();
+    change(&mut tags);
+    if !tags.is_empty() {
+        settings.pack_tags.insert(key, tags.clone());
+    }
+    *state.settings.write() = settings.clone();
+    save_settings_to_file(&settings)?;
+    Ok(tags)
+}
+
+#[tauri::command]
+fn add_tag(uuid: String, tag: String, app: AppHandle) -> Result<Vec<String>, String> {
+    let tag = tag.trim().to_string();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+    update_pack_tags(&app, &uuid, |tags| {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+            tags.push(tag);
+        }
+    })
+}
+
+#[tauri::command]
+fn remove_tag(uuid: String, tag: String, app: AppHandle) -> Result<Vec<String>, String> {
+    let tag = tag.trim();
+    update_pack_tags(&app, &uuid, |tags| tags.retain(|t| !t.eq_ignore_ascii_case(tag)))
+}
+
+#[tauri::command]
+fn get_tags(uuid: String, app: AppHandle) -> Vec<String> {
+    app.state::<AppState>().settings.read()
+        .pack_tags
+        .get(&uuid.trim().to_lowercase())
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+fn filter_by_tag(packs: Vec<PackInfo>, tag: String, app: AppHandle) -> Vec<PackInfo> {
+    let mut packs = packs;
+    apply_pack_tags(&app, &mut packs);
+    let tag = tag.trim();
+    packs.retain(|p| p.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+    packs
+}
+
 #[tauri::command]
 fn check_toolcoin_installed() -> bool {
     let toolcoin_path = std::path::Path::new("C:\\Program Files\\alphtoolcoin\\ToolCoin.exe");
@@ -1134,19 +3346,80 @@ fn launch_toolcoin(app: AppHandle) -> Result<(), String> {
     }
 }
 
-#[tauri::command]
-fn delete_all_packs(app: AppHandle) -> Result<(), String> {
-    let state = app.state::<AppState>();
-    let settings = state.settings.read().clone();
-    
-    let folders = vec![
+/// The folders delete_all_packs empties.
+fn delete_all_folders(settings: &Settings) -> Vec<(&'static str, Option<String>)> {
+    vec![
         ("Behavior Packs", settings.behavior_pack_path.clone()),
         ("Resource Packs", settings.resource_pack_path.clone()),
         ("Skin Packs", settings.skin_pack_path.clone()),
         ("World Templates", settings.world_template_path.clone()),
-    ];
+    ]
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteFolderPreview {
+    pub label: String,
+    pub path: String,
+    pub pack_count: usize,
+    pub size: u64,
+    pub size_formatted: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteAllPreview {
+    pub folders: Vec<DeleteFolderPreview>,
+    pub total_packs: usize,
+    pub total_size: u64,
+    pub total_size_formatted: String,
+}
+
+/// What delete_all_packs would remove, without touching anything.
+#[tauri::command]
+async fn preview_delete_all(app: AppHandle) -> Result<DeleteAllPreview, String> {
+    let folders = delete_all_folders(&app.state::<AppState>().settings.read());
+    tokio::task::spawn_blocking(move || {
+        use rayon::prelude::*;
+        let folders: Vec<DeleteFolderPreview> = folders
+            .into_iter()
+            .filter_map(|(label, path)| {
+                let path = path?;
+                let dirs: Vec<PathBuf> = std::fs::read_dir(&path)
+                    .ok()?
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect();
+                let size: u64 = dirs.par_iter().map(|dir| calculate_folder_size(dir)).sum();
+                Some(DeleteFolderPreview {
+                    label: label.to_string(),
+                    path,
+                    pack_count: dirs.len(),
+                    size,
+                    size_formatted: format_bytes(size),
+                })
+            })
+            .collect();
+        let total_packs = folders.iter().map(|f| f.pack_count).sum();
+        let total_size = folders.iter().map(|f| f.size).sum();
+        DeleteAllPreview {
+            folders,
+            total_packs,
+            total_size,
+            total_size_formatted: format_bytes(total_size),
+        }
+    })
+    .await
+    .map_err(|e| format!("Delete preview failed: {}", e))
+}
+
+#[tauri::command]
+async fn delete_all_packs(app: AppHandle) -> Result<(), String> {
+    ensure_not_safe_mode(&app)?;
+    warn_if_minecraft_running_async(&app).await;
+    let state = app.state::<AppState>();
+    let settings = state.settings.read().clone();
     
-    for (name, path_opt) in folders {
+    for (name, path_opt) in delete_all_folders(&settings) {
         if let Some(path_str) = path_opt {
             let path = std::path::Path::new(&path_str);
             if path.exists() {
@@ -1168,8 +3441,97 @@ fn delete_all_packs(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+fn is_hidden_entry(path: &std::path::Path) -> bool {
+    let dot_named = path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.starts_with('.'))
+        .unwrap_or(false);
+    
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if let Ok(metadata) = std::fs::metadata(path) {
+            if metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0 {
+                return true;
+            }
+        }
+    }
+    
+    dot_named
+}
+
+/// True when a folder tree holds no files at all and no hidden entries.
+fn is_recursively_empty(path: &std::path::Path) -> bool {
+    let mut stack = vec![path.to_path_buf()];
+    
+    while let Some(current) = stack.pop() {
+        let entries = match std::fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(_) => return false,
+        };
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            if is_hidden_entry(&entry_path) {
+                return false;
+            }
+            match entry.file_type() {
+                Ok(ft) if ft.is_dir() => stack.push(entry_path),
+                _ => return false,
+            }
+        }
+    }
+    
+    true
+}
+
+#[tauri::command]
+fn clean_empty_pack_folders(app: AppHandle) -> Result<Vec<String>, String> {
+    ensure_not_safe_mode(&app)?;
+    let settings = app.state::<AppState>().settings.read().clone();
+    let roots = [
+        settings.behavior_pack_path,
+        settings.resource_pack_path,
+        settings.skin_pack_path,
+        settings.world_template_path,
+    ];
+    
+    let mut removed = Vec::new();
+    
+    for root in roots.iter().flatten() {
+        let root_path = std::path::Path::new(root);
+        let entries = match std::fs::read_dir(root_path) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            // Only immediate pack folders are candidates, never the configured root
+            if !entry_path.is_dir() || entry_path == root_path || is_hidden_entry(&entry_path) {
+                continue;
+            }
+            if !is_recursively_empty(&entry_path) {
+                continue;
+            }
+            
+            match std::fs::remove_dir_all(&entry_path) {
+                Ok(()) => {
+                    emit_log(&app, "INFO", &format!("Removed empty folder: {}", entry_path.display()));
+                    removed.push(entry_path.to_string_lossy().to_string());
+                }
+                Err(e) => emit_log(&app, "WARN", &format!("Failed to remove {}: {}", entry_path.display(), e)),
+            }
+        }
+    }
+    
+    emit_log(&app, "SUCCESS", &format!("Removed {} empty pack folders", removed.len()));
+    Ok(removed)
+}
+
 #[tauri::command]
-async fn get_directory_folders(_app: AppHandle) -> Result<Vec<PackInfo>, String> {
+async fn get_directory_folders(app: AppHandle) -> Result<Vec<PackInfo>, String> {
+    let icon_names = app.state::<AppState>().settings.read().icon_names();
     // Build correlation set scanning ALL candidate MC paths.
     let correlated = build_correlated_mashup_bases(&None, &None, &None);
 
@@ -1181,48 +3543,65 @@ async fn get_directory_folders(_app: AppHandle) -> Result<Vec<PackInfo>, String>
         ("WorldTemplate","world_templates"),
     ];
 
-    let mut folder_paths: Vec<(String, String, String)> = Vec::new();
+    let mut folder_paths: Vec<(String, String, String, Option<String>)> = Vec::new();
     let mut seen_canonical = std::collections::HashSet::new();
 
     for (type_str, subfolder) in pack_subfolders {
         for path_str in all_mc_subfolder_paths(subfolder) {
             let path = std::path::Path::new(&path_str);
             if path.exists() && path.is_dir() {
-                if let Ok(entries) = std::fs::read_dir(path) {
-                    for entry in entries.flatten() {
-                        let entry_path = entry.path();
-                        if entry_path.is_dir() {
-                            // Deduplicate via canonical path.
-                            let canonical = entry_path.canonicalize()
-                                .unwrap_or_else(|_| entry_path.clone());
-                            if !seen_canonical.insert(canonical) { continue; }
-
-                            let folder_name = entry_path
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("Unknown")
-                                .to_string();
-                            folder_paths.push((
-                                entry_path.to_string_lossy().to_string(),
-                                folder_name,
-                                type_str.to_string(),
-                            ));
-                        }
-                    }
+                for entry_path in pack_dirs_in(path) {
+                    // Deduplicate via canonical path.
+                    let Some(canonical) = resolve_pack_dir(&entry_path) else { continue };
+                    if !seen_canonical.insert(canonical) { continue; }
+
+                    let folder_name = entry_path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("Unknown")
+                        .to_string();
+                    // Packs inside category folders report the folders they sit in
+                    let category = entry_path
+                        .parent()
+                        .and_then(|parent| parent.strip_prefix(path).ok())
+                        .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+                        .filter(|rel| !rel.is_empty());
+                    folder_paths.push((
+                        entry_path.to_string_lossy().to_string(),
+                        folder_name,
+                        type_str.to_string(),
+                        category,
+                    ));
                 }
             }
         }
     }
 
-    let all_folders: Vec<PackInfo> = tokio::task::spawn_blocking(move || {
+    let total_folders = folder_paths.len();
+    let _ = app.emit("progress", serde_json::json!({
+        "current": 0,
+        "total": total_folders,
+        "message": "Reading installed packs...",
+        "phase": "loading-installed"
+    }));
+
+    let progress = BatchProgress::new(&app, total_folders, 25, "Read", "loading-installed");
+    let app_for_log = app.clone();
+
+    let mut all_folders: Vec<PackInfo> = tokio::task::spawn_blocking(move || {
         use rayon::prelude::*;
 
         let mut final_results: Vec<PackInfo> = folder_paths
             .into_par_iter()
-            .map(|(path, folder_name, pack_type_str)| {
+            .map(|(path, folder_name, pack_type_str, category)| {
+                progress.tick();
+
                 let entry_path = std::path::Path::new(&path);
-                let (uuid, display_name, version) = read_pack_metadata_fast(entry_path);
-                let icon = read_pack_icon(entry_path);
+                let metadata = read_pack_metadata_fast(entry_path);
+                if metadata.lenient_manifest {
+                    log_lenient_manifest(&app_for_log, entry_path);
+                }
+                let icon = read_pack_icon(entry_path, &icon_names);
                 // Only world template folders can be promoted to MashupPack.
                 // RP/SP/BP entries that share a name with a mashup keep their own type
                 // so the frontend can correctly group and display them as children.
@@ -1233,20 +3612,31 @@ async fn get_directory_folders(_app: AppHandle) -> Result<Vec<PackInfo>, String>
                 };
                 PackInfo {
                     path: path.clone(),
-                    name: display_name.unwrap_or_else(|| folder_name.clone()),
+                    name: metadata.name.unwrap_or_else(|| folder_name.clone()),
+                    file_name: None,
                     pack_type,
-                    uuid,
-                    version,
+                    uuid: metadata.uuid,
+                    version: metadata.version,
                     extracted: false,
                     icon_base64: icon,
                     subfolder: None,
                     folder_size: None,
                     folder_size_formatted: None,
+                    uncompressed_size: None,
                     needs_attention: None,
                     attention_message: None,
+                    attention_reasons: Vec::new(),
                     is_installed: None,
                     is_update: None,
                     installed_version: None,
+                    dependencies: Vec::new(),
+                    description: metadata.description,
+                    authors: metadata.authors,
+                    format_version: metadata.format_version,
+                    category,
+                    linked_to: None,
+                    receipt: read_receipt(entry_path),
+                    tags: Vec::new(),
                 }
             })
             .collect();
@@ -1255,53 +3645,29 @@ async fn get_directory_folders(_app: AppHandle) -> Result<Vec<PackInfo>, String>
         final_results
     }).await.map_err(|e| e.to_string())?;
 
+    apply_pack_tags(&app, &mut all_folders);
     Ok(all_folders)
 }
 
-fn read_pack_icon(folder_path: &std::path::Path) -> Option<String> {
-    let icon_names = ["pack_icon.png", "Pack_Icon.png", "world_icon.jpeg", "world_icon.jpg", "icon.png"];
+fn read_pack_icon(folder_path: &std::path::Path, icon_names: &[String]) -> Option<String> {
+    cached_folder_icon(folder_path, icon_names, &icon_names.join(","), || read_pack_icon_uncached(folder_path, icon_names))
+}
+
+fn read_pack_icon_uncached(folder_path: &std::path::Path, icon_names: &[String]) -> Option<String> {
     // 64 MB hard cap — anything larger is almost certainly corrupt/wrong
     const MAX_ICON_SIZE: u64 = 64 * 1024 * 1024;
-    const MAX_DIMENSION: u32 = 256;
 
-    for icon_name in &icon_names {
+    for icon_name in icon_names {
         let icon_path = folder_path.join(icon_name);
         if icon_path.exists() {
             let file_size = icon_path.metadata().map(|m| m.len()).unwrap_or(u64::MAX);
-            if file_size > MAX_ICON_SIZE {
-                continue;
-            }
-            if let Ok(icon_data) = std::fs::read(&icon_path) {
-                // If the image fits within our dimension limit, encode it directly
-                // without a full decode/re-encode cycle (fast path).
-                // For oversized files we decode, resize, and re-encode as PNG.
-                let is_jpeg = icon_name.ends_with(".jpg") || icon_name.ends_with(".jpeg");
-
-                // Attempt a fast path: decode just the dimensions.
-                let needs_resize = if let Ok(reader) = image::ImageReader::new(std::io::Cursor::new(&icon_data)).with_guessed_format() {
-                    if let Ok((w, h)) = reader.into_dimensions() {
-                        w > MAX_DIMENSION || h > MAX_DIMENSION
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                };
-
-                if !needs_resize {
-                    let mime = if is_jpeg { "image/jpeg" } else { "image/png" };
-                    let b64 = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &icon_data);
-                    return Some(format!("data:{};base64,{}", mime, b64));
-                }
-
-                // Slow path: decode → resize → re-encode as PNG
-                if let Ok(img) = image::load_from_memory(&icon_data) {
-                    let resized = img.resize(MAX_DIMENSION, MAX_DIMENSION, image::imageops::FilterType::Lanczos3);
-                    let mut buf = Vec::new();
-                    if resized.write_to(&mut std::io::Cursor::new(&mut buf), image::ImageFormat::Png).is_ok() {
-                        let b64 = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &buf);
-                        return Some(format!("data:image/png;base64,{}", b64));
-                    }
+            if file_size > MAX_ICON_SIZE {
+                continue;
+            }
+            if let Ok(icon_data) = std::fs::read(&icon_path) {
+                // Undecodable formats fall through to the next candidate name
+                if let Some(icon) = encode_icon_data(&icon_data, icon_name) {
+                    return Some(icon);
                 }
             }
         }
@@ -1310,12 +3676,25 @@ fn read_pack_icon(folder_path: &std::path::Path) -> Option<String> {
     None
 }
 
-fn read_pack_metadata_fast(folder_path: &std::path::Path) -> (Option<String>, Option<String>, Option<String>) {
+#[derive(Default)]
+struct PackMetadata {
+    uuid: Option<String>,
+    name: Option<String>,
+    version: Option<String>,
+    description: Option<String>,
+    authors: Vec<String>,
+    format_version: Option<u32>,
+    dependencies: Vec<String>,
+    /// manifest.json only parsed once comments and trailing commas were allowed
+    lenient_manifest: bool,
+}
+
+fn read_pack_metadata_fast(folder_path: &std::path::Path) -> PackMetadata {
     let manifest_path = folder_path.join("manifest.json");
     
     if manifest_path.exists() {
         if let Ok(content) = std::fs::read_to_string(&manifest_path) {
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
+            if let Some((json, lenient_manifest)) = parse_manifest_json_lenient(&content) {
                 let uuid = json.get("header")
                     .and_then(|h| h.get("uuid"))
                     .and_then(|u| u.as_str())
@@ -1344,12 +3723,21 @@ fn read_pack_metadata_fast(folder_path: &std::path::Path) -> (Option<String>, Op
                         }
                     });
                 
-                return (uuid, name, version);
+                return PackMetadata {
+                    uuid,
+                    name,
+                    version,
+                    description: extract_description(&json),
+                    authors: extract_authors(&json),
+                    format_version: extract_format_version(&json),
+                    dependencies: extract_dependencies(&json),
+                    lenient_manifest,
+                };
             }
         }
     }
     
-    (None, None, None)
+    PackMetadata::default()
 }
 
 fn extract_base_name(name: &str) -> String {
@@ -1456,6 +3844,7 @@ struct InstalledPackInfo {
     version: Option<String>,
     path: String,
     folder_name: String,
+    source: Option<String>,
 }
 
 fn get_installed_packs_info(_app: &AppHandle) -> Vec<InstalledPackInfo> {
@@ -1476,37 +3865,33 @@ fn get_installed_packs_info(_app: &AppHandle) -> Vec<InstalledPackInfo> {
         for path_str in all_mc_subfolder_paths(subfolder) {
             let path = std::path::Path::new(&path_str);
             if path.exists() && path.is_dir() {
-                if let Ok(entries) = std::fs::read_dir(path) {
-                    for entry in entries.flatten() {
-                        let entry_path = entry.path();
-                        if entry_path.is_dir() {
-                            let canonical = entry_path.canonicalize()
-                                .unwrap_or_else(|_| entry_path.clone());
-                            if !seen_canonical.insert(canonical) { continue; }
-
-                            let folder_name = entry_path
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("Unknown")
-                                .to_string();
-
-                            let (uuid, display_name, version) = read_pack_metadata_fast(&entry_path);
-
-                            let pack_type = if *pack_type_str == "WorldTemplate" && is_mashup(&folder_name, &correlated) {
-                                PackType::MashupPack
-                            } else {
-                                parse_pack_type(pack_type_str)
-                            };
-
-                            installed_packs.push(InstalledPackInfo {
-                                uuid,
-                                name: display_name.unwrap_or_else(|| folder_name.clone()),
-                                pack_type,
-                                version,
-                                path: entry_path.to_string_lossy().to_string(),
-                                folder_name,
-                            });
-                        }
+                for entry_path in pack_dirs_in(path) {
+                    if let Some(canonical) = resolve_pack_dir(&entry_path) {
+                        if !seen_canonical.insert(canonical) { continue; }
+
+                        let folder_name = entry_path
+                            .file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("Unknown")
+                            .to_string();
+
+                        let PackMetadata { uuid, name: display_name, version, .. } = read_pack_metadata_fast(&entry_path);
+
+                        let pack_type = if *pack_type_str == "WorldTemplate" && is_mashup(&folder_name, &correlated) {
+                            PackType::MashupPack
+                        } else {
+                            parse_pack_type(pack_type_str)
+                        };
+
+                        installed_packs.push(InstalledPackInfo {
+                            uuid,
+                            name: display_name.unwrap_or_else(|| folder_name.clone()),
+                            pack_type,
+                            version,
+                            path: entry_path.to_string_lossy().to_string(),
+                            folder_name,
+                            source: read_receipt(&entry_path).map(|r| r.source),
+                        });
                     }
                 }
             }
@@ -1516,20 +3901,477 @@ fn get_installed_packs_info(_app: &AppHandle) -> Vec<InstalledPackInfo> {
     installed_packs
 }
 
+/// Matches scanned packs to installed ones the way compute_pack_status does.
+struct InstalledLookup<'a> {
+    installed: &'a [InstalledPackInfo],
+    by_uuid: std::collections::HashMap<&'a str, usize>,
+    // Several installs can share a base name, e.g. "Pack v1" and "Pack v2"
+    by_base_name: std::collections::HashMap<(PackType, String), Vec<usize>>,
+    by_source: std::collections::HashMap<&'a str, usize>,
+}
+
+impl<'a> InstalledLookup<'a> {
+    fn new(installed_packs: &'a [InstalledPackInfo]) -> Self {
+        let mut by_base_name: std::collections::HashMap<(PackType, String), Vec<usize>> = std::collections::HashMap::new();
+        for (idx, ip) in installed_packs.iter().enumerate() {
+            by_base_name.entry((ip.pack_type, extract_base_name(&ip.name))).or_default().push(idx);
+        }
+        InstalledLookup {
+            installed: installed_packs,
+            by_uuid: installed_packs
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, ip)| ip.uuid.as_deref().map(|u| (u, idx)))
+                .collect(),
+            by_base_name,
+            by_source: installed_packs
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, ip)| ip.source.as_deref().map(|s| (s, idx)))
+                .collect(),
+        }
+    }
+
+    fn find(&self, pack: &PackInfo) -> Option<usize> {
+        // A receipt naming this exact source file beats any uuid or name guess
+        if let Some(&idx) = self.by_source.get(pack.path.as_str()) {
+            Some(idx)
+        } else if let Some(uuid) = pack.uuid.as_deref() {
+            self.by_uuid.get(uuid).copied()
+        } else {
+            let pack_base = extract_base_name(&pack.name);
+            let candidates = self.by_base_name.get(&(pack.pack_type, pack_base))?;
+            let pack_version = pack.version.clone()
+                .or_else(|| extract_version_from_name(&pack.name))
+                .or_else(|| extract_version_from_path(&pack.path))
+                .map(|v| version_parts(&v));
+            let Some(pack_version) = pack_version else {
+                return candidates.first().copied();
+            };
+            candidates.iter().copied().min_by_key(|&idx| {
+                let installed = &self.installed[idx];
+                installed.version.clone()
+                    .or_else(|| extract_version_from_name(&installed.name))
+                    .or_else(|| extract_version_from_path(&installed.path))
+                    .map(|v| version_distance(&pack_version, &version_parts(&v)))
+                    .unwrap_or((usize::MAX, u64::MAX))
+            })
+        }
+    }
+}
+
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .split(['.', '-', '_'])
+        .map(|part| part.chars().take_while(|c| c.is_ascii_digit()).collect::<String>().parse().unwrap_or(0))
+        .collect()
+}
+
+/// Smaller is closer: components left after the shared prefix, then the gap at
+/// the first component that differs.
+fn version_distance(a: &[u64], b: &[u64]) -> (usize, u64) {
+    let len = a.len().max(b.len());
+    let part = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    match (0..len).find(|&i| part(a, i) != part(b, i)) {
+        Some(i) => (len - i, part(a, i).abs_diff(part(b, i))),
+        None => (0, 0),
+    }
+}
+
+const NEAR_DUPLICATE_THRESHOLD: f64 = 0.8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NearDuplicateMember {
+    pub path: String,
+    pub name: String,
+    pub uuid: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NearDuplicateGroup {
+    pub pack_type: PackType,
+    pub similarity: f64,
+    pub packs: Vec<NearDuplicateMember>,
+}
+
+fn find_root(parents: &mut [usize], idx: usize) -> usize {
+    let mut root = idx;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[idx] = root;
+    root
+}
+
+#[tauri::command]
+async fn find_near_duplicates(app: AppHandle) -> Result<Vec<NearDuplicateGroup>, String> {
+    let app_for_task = app.clone();
+    let groups = tokio::task::spawn_blocking(move || {
+        use rayon::prelude::*;
+
+        let installed = get_installed_packs_info(&app_for_task);
+        let state = app_for_task.state::<AppState>();
+        let cache = &state.fingerprint_cache;
+
+        // Fingerprints are reused until the pack folder's mtime changes
+        let fingerprints: Vec<Arc<std::collections::HashSet<u64>>> = installed
+            .par_iter()
+            .map(|pack| {
+                let path = std::path::Path::new(&pack.path);
+                let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+                if let Some(modified) = modified {
+                    if let Some((cached_at, fingerprint)) = cache.lock().get(&pack.path) {
+                        if *cached_at == modified {
+                            return Arc::clone(fingerprint);
+                        }
+                    }
+                }
+                let fingerprint = Arc::new(fingerprint_folder(path));
+                if let Some(modified) = modified {
+                    cache.lock().insert(pack.path.clone(), (modified, Arc::clone(&fingerprint)));
+                }
+                fingerprint
+            })
+            .collect();
+
+        let mut parents: Vec<usize> = (0..installed.len()).collect();
+        let mut link_scores: Vec<(usize, f64)> = Vec::new();
+        for i in 0..installed.len() {
+            for j in (i + 1)..installed.len() {
+                let (a, b) = (&installed[i], &installed[j]);
+                // Exact UUID matches are ordinary duplicates, not near-duplicates
+                if a.pack_type != b.pack_type || (a.uuid.is_some() && a.uuid == b.uuid) {
+                    continue;
+                }
+                let similarity = fingerprint_similarity(&fingerprints[i], &fingerprints[j]);
+                if similarity >= NEAR_DUPLICATE_THRESHOLD {
+                    let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+                    parents[root_j] = root_i;
+                    link_scores.push((i, similarity));
+                }
+            }
+        }
+
+        let mut members: std::collections::BTreeMap<usize, Vec<usize>> = std::collections::BTreeMap::new();
+        for idx in 0..installed.len() {
+            let root = find_root(&mut parents, idx);
+            members.entry(root).or_default().push(idx);
+        }
+        let mut scores: std::collections::HashMap<usize, Vec<f64>> = std::collections::HashMap::new();
+        for (idx, similarity) in link_scores {
+            let root = find_root(&mut parents, idx);
+            scores.entry(root).or_default().push(similarity);
+        }
+
+        let mut groups: Vec<NearDuplicateGroup> = members
+            .into_iter()
+            .filter(|(_, idxs)| idxs.len() > 1)
+            .map(|(root, idxs)| {
+                let group_scores = scores.get(&root).cloned().unwrap_or_default();
+                let average = group_scores.iter().sum::<f64>() / group_scores.len().max(1) as f64;
+                NearDuplicateGroup {
+                    pack_type: installed[root].pack_type,
+                    similarity: (average * 1000.0).round() / 10.0,
+                    packs: idxs
+                        .into_iter()
+                        .map(|idx| NearDuplicateMember {
+                            path: installed[idx].path.clone(),
+                            name: installed[idx].name.clone(),
+                            uuid: installed[idx].uuid.clone(),
+                            version: installed[idx].version.clone(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        groups.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        groups
+    })
+    .await
+    .map_err(|e| format!("Near-duplicate search failed: {}", e))?;
+
+    emit_log(&app, "INFO", &format!("Found {} groups of near-duplicate packs", groups.len()));
+    Ok(groups)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddonPair {
+    pub name: String,
+    pub behavior_pack: Option<NearDuplicateMember>,
+    pub resource_pack: Option<NearDuplicateMember>,
+    /// "dependency" or "name"; None when no resource pack was found
+    pub matched_by: Option<String>,
+    /// Declared dependency UUIDs of the behavior pack that aren't installed
+    pub missing_dependencies: Vec<String>,
+}
+
+/// Pairs installed behavior packs with the resource pack of the same addon,
+/// by a dependency UUID in either manifest or else by base name. Behavior
+/// packs with uninstalled dependencies are listed on their own.
+#[tauri::command]
+async fn find_addon_pairs(app: AppHandle) -> Result<Vec<AddonPair>, String> {
+    let app_for_task = app.clone();
+    let pairs = tokio::task::spawn_blocking(move || {
+        let installed = get_installed_packs_info(&app_for_task);
+        let dependencies: Vec<Vec<String>> = installed
+            .iter()
+            .map(|pack| match pack.pack_type {
+                PackType::BehaviorPack | PackType::ResourcePack => {
+                    read_pack_metadata_fast(std::path::Path::new(&pack.path)).dependencies
+                }
+                _ => Vec::new(),
+            })
+            .collect();
+        let installed_uuids: std::collections::HashSet<String> = installed
+            .iter()
+            .filter_map(|p| p.uuid.as_ref().map(|u| u.to_lowercase()))
+            .collect();
+        let uuid_of = |idx: usize| installed[idx].uuid.as_ref().map(|u| u.to_lowercase());
+        let member = |idx: usize| NearDuplicateMember {
+            path: installed[idx].path.clone(),
+            name: installed[idx].name.clone(),
+            uuid: installed[idx].uuid.clone(),
+            version: installed[idx].version.clone(),
+        };
+
+        let resource_packs: Vec<usize> = (0..installed.len())
+            .filter(|&idx| installed[idx].pack_type == PackType::ResourcePack)
+            .collect();
+        let mut paired = std::collections::HashSet::new();
+        let mut pairs = Vec::new();
+
+        for bp in (0..installed.len()).filter(|&idx| installed[idx].pack_type == PackType::BehaviorPack) {
+            let bp_uuid = uuid_of(bp);
+            let by_dependency = resource_packs.iter().copied().find(|&rp| {
+                !paired.contains(&rp)
+                    && (uuid_of(rp).is_some_and(|u| dependencies[bp].contains(&u))
+                        || bp_uuid.as_ref().is_some_and(|u| dependencies[rp].contains(u)))
+            });
+            let bp_base = extract_base_name(&installed[bp].name);
+            let (rp, matched_by) = match by_dependency {
+                Some(rp) => (Some(rp), Some("dependency")),
+                None => {
+                    let by_name = resource_packs.iter().copied().find(|&rp| {
+                        !paired.contains(&rp) && extract_base_name(&installed[rp].name) == bp_base
+                    });
+                    (by_name, by_name.map(|_| "name"))
+                }
+            };
+            let missing_dependencies: Vec<String> = dependencies[bp]
+                .iter()
+                .filter(|u| !installed_uuids.contains(*u))
+                .cloned()
+                .collect();
+            if rp.is_none() && missing_dependencies.is_empty() {
+                continue;
+            }
+            if let Some(rp) = rp {
+                paired.insert(rp);
+            }
+            pairs.push(AddonPair {
+                name: installed[bp].name.clone(),
+                behavior_pack: Some(member(bp)),
+                resource_pack: rp.map(member),
+                matched_by: matched_by.map(str::to_string),
+                missing_dependencies,
+            });
+        }
+
+        pairs.sort_by_key(|p| p.name.to_lowercase());
+        pairs
+    })
+    .await
+    .map_err(|e| format!("Addon pairing failed: {}", e))?;
+
+    let incomplete = pairs.iter().filter(|p| p.resource_pack.is_none()).count();
+    emit_log(&app, "INFO", &format!("Found {} addon pairs ({} missing a resource pack)", pairs.len() - incomplete, incomplete));
+    Ok(pairs)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivePack {
+    pub pack_id: String,
+    pub version: Option<String>,
+    pub pack_type: PackType,
+    pub installed: bool,
+    pub installed_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldActivePacks {
+    pub world_path: String,
+    pub active: Vec<ActivePack>,
+    pub inactive_installed: Vec<String>,
+}
+
+fn read_world_pack_list(path: &std::path::Path) -> Vec<(String, Option<String>)> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+    let json = match serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{feff}')) {
+        Ok(j) => j,
+        Err(_) => return Vec::new(),
+    };
+    
+    json.as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let pack_id = entry.get("pack_id").and_then(|p| p.as_str())?.to_lowercase();
+                    let version = entry.get("version").and_then(|v| {
+                        if let Some(arr) = v.as_array() {
+                            Some(
+                                arr.iter()
+                                    .filter_map(|n| n.as_u64())
+                                    .map(|n| n.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join("."),
+                            )
+                        } else {
+                            v.as_str().map(|s| s.to_string())
+                        }
+                    });
+                    Some((pack_id, version))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+async fn read_world_active_packs(world_path: String, app: AppHandle) -> Result<WorldActivePacks, String> {
+    let world = std::path::PathBuf::from(&world_path);
+    if !world.is_dir() {
+        return Err(format!("World folder does not exist: {}", world_path));
+    }
+    if !world.join("level.dat").exists() {
+        return Err("Folder is not a Minecraft world (no level.dat)".to_string());
+    }
+    
+    tokio::task::spawn_blocking(move || {
+        let installed = get_installed_packs_info(&app);
+        let mut installed_by_uuid: std::collections::HashMap<String, &InstalledPackInfo> = installed
+            .iter()
+            .filter_map(|ip| ip.uuid.as_ref().map(|u| (u.to_lowercase(), ip)))
+            .collect();
+        
+        // Packs bundled inside the world folder count as installed for that world
+        let mut world_local: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+        for sub in ["behavior_packs", "resource_packs"] {
+            if let Ok(entries) = std::fs::read_dir(world.join(sub)) {
+                for entry in entries.flatten() {
+                    let PackMetadata { uuid, name, .. } = read_pack_metadata_fast(&entry.path());
+                    if let Some(uuid) = uuid {
+                        let folder = entry.file_name().to_string_lossy().to_string();
+                        world_local.insert(uuid.to_lowercase(), name.unwrap_or(folder));
+                    }
+                }
+            }
+        }
+        
+        let lists = [
+            ("world_behavior_packs.json", PackType::BehaviorPack),
+            ("world_resource_packs.json", PackType::ResourcePack),
+        ];
+        
+        let mut active = Vec::new();
+        let mut active_ids = std::collections::HashSet::new();
+        for (file, pack_type) in lists {
+            for (pack_id, version) in read_world_pack_list(&world.join(file)) {
+                active_ids.insert(pack_id.clone());
+                let installed_name = installed_by_uuid.get(&pack_id)
+                    .map(|ip| ip.name.clone())
+                    .or_else(|| world_local.get(&pack_id).cloned());
+                active.push(ActivePack {
+                    installed: installed_name.is_some(),
+                    installed_name,
+                    pack_id,
+                    version,
+                    pack_type,
+                });
+            }
+        }
+        
+        installed_by_uuid.retain(|uuid, ip| {
+            !active_ids.contains(uuid)
+                && matches!(ip.pack_type, PackType::BehaviorPack | PackType::ResourcePack)
+        });
+        let mut inactive_installed: Vec<String> = installed_by_uuid
+            .values()
+            .map(|ip| ip.name.clone())
+            .collect();
+        inactive_installed.sort_by_key(|n| n.to_lowercase());
+        
+        WorldActivePacks {
+            world_path: world.to_string_lossy().to_string(),
+            active,
+            inactive_installed,
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to read world packs: {}", e))
+}
+
+/// "progress" events for a parallel batch: one every `every` completions and
+/// one for the last item, like scan_packs sends while scanning.
+struct BatchProgress {
+    app: AppHandle,
+    total: usize,
+    every: usize,
+    label: &'static str,
+    phase: &'static str,
+    done: std::sync::atomic::AtomicUsize,
+    last_emit: std::sync::atomic::AtomicUsize,
+}
+
+impl BatchProgress {
+    fn new(app: &AppHandle, total: usize, every: usize, label: &'static str, phase: &'static str) -> Self {
+        BatchProgress {
+            app: app.clone(),
+            total,
+            every,
+            label,
+            phase,
+            done: std::sync::atomic::AtomicUsize::new(0),
+            last_emit: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    fn tick(&self) {
+        let current = self.done.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        let last = self.last_emit.load(std::sync::atomic::Ordering::SeqCst);
+        if current == self.total || current.saturating_sub(last) >= self.every {
+            self.last_emit.store(current, std::sync::atomic::Ordering::SeqCst);
+            let _ = self.app.emit("progress", serde_json::json!({
+                "current": current,
+                "total": self.total,
+                "message": format!("{} {}/{}", self.label, current, self.total),
+                "phase": self.phase
+            }));
+        }
+    }
+}
+
 #[tauri::command]
-async fn get_all_folder_sizes(paths: Vec<String>) -> Result<Vec<(String, u64, String)>, String> {
+async fn get_all_folder_sizes(paths: Vec<String>, app: AppHandle) -> Result<Vec<(String, u64, String)>, String> {
+    let progress = BatchProgress::new(&app, paths.len(), 25, "Sized", "sizing");
     let results: Vec<(String, u64, String)> = tokio::task::spawn_blocking(move || {
         use rayon::prelude::*;
         paths.into_par_iter()
             .filter_map(|path| {
                 let folder_path = std::path::Path::new(&path);
-                if folder_path.exists() && folder_path.is_dir() {
+                let result = if folder_path.exists() && folder_path.is_dir() {
                     let size = calculate_folder_size(folder_path);
                     let formatted = format_bytes(size);
                     Some((path, size, formatted))
                 } else {
                     None
-                }
+                };
+                progress.tick();
+                result
             })
             .collect()
     }).await.map_err(|e| e.to_string())?;
@@ -1565,91 +4407,458 @@ fn is_within_configured_dirs(path: &std::path::Path, app: &AppHandle) -> bool {
     .cloned()
     .collect();
 
-    let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-    configured.iter().any(|dir| {
-        let base = std::path::Path::new(dir);
-        let canonical_base = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
-        canonical_path.starts_with(&canonical_base)
+    let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    configured.iter().any(|dir| {
+        let base = std::path::Path::new(dir);
+        let canonical_base = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
+        canonical_path.starts_with(&canonical_base)
+    })
+}
+
+#[tauri::command]
+async fn delete_pack(path: String, app: AppHandle) -> Result<(), String> {
+    ensure_not_safe_mode(&app)?;
+    let folder_path = std::path::Path::new(&path);
+    if !is_within_configured_dirs(folder_path, &app) {
+        return Err("Path is outside configured pack directories".to_string());
+    }
+    if !folder_path.exists() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+    warn_if_minecraft_running_async(&app).await;
+    
+    std::fs::remove_dir_all(folder_path)
+        .map_err(|e| format!("Failed to delete pack: {}", e))
+}
+
+#[tauri::command]
+fn move_pack(path: String, destination: String, app: AppHandle) -> Result<String, String> {
+    ensure_not_safe_mode(&app)?;
+    let source_path = std::path::Path::new(&path);
+    let dest_path = std::path::Path::new(&destination);
+    
+    if !is_within_configured_dirs(source_path, &app) {
+        return Err("Source path is outside configured pack directories".to_string());
+    }
+    if !is_within_configured_dirs(dest_path, &app) {
+        return Err("Destination is outside configured pack directories".to_string());
+    }
+
+    if !source_path.exists() {
+        return Err(format!("Source path does not exist: {}", path));
+    }
+    
+    let folder_name = source_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Unknown");
+    
+    let final_dest = dest_path.join(folder_name);
+    
+    if final_dest.exists() {
+        return Err(format!("Destination already exists: {}", final_dest.display()));
+    }
+    
+    std::fs::rename(source_path, &final_dest)
+        .map_err(|e| format!("Failed to move pack: {}", e))?;
+    
+    Ok(final_dest.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn rename_pack(path: String, new_name: String, app: AppHandle) -> Result<String, String> {
+    ensure_not_safe_mode(&app)?;
+    if new_name.contains('/') || new_name.contains('\\') || new_name.contains("..") {
+        return Err("Invalid name: must not contain path separators or '..'".to_string());
+    }
+    let folder_path = std::path::Path::new(&path);
+    if !is_within_configured_dirs(folder_path, &app) {
+        return Err("Path is outside configured pack directories".to_string());
+    }
+    if !folder_path.exists() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+    
+    let parent = folder_path.parent()
+        .ok_or("Cannot rename root directory")?;
+    
+    let new_path = parent.join(&new_name);
+    
+    if new_path.exists() {
+        return Err(format!("A folder named '{}' already exists", new_name));
+    }
+    
+    std::fs::rename(folder_path, &new_path)
+        .map_err(|e| format!("Failed to rename pack: {}", e))?;
+    
+    Ok(new_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn pack_content_profile(path: String) -> Result<ContentProfile, String> {
+    tokio::task::spawn_blocking(move || profile_pack_contents(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn read_pack_readme(path: String) -> Result<Option<String>, String> {
+    tokio::task::spawn_blocking(move || find_pack_readme(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+const PACK_ICON_SIZE: (u32, u32) = (256, 256);
+const WORLD_ICON_SIZE: (u32, u32) = (800, 450);
+
+fn is_world_template_folder(folder: &std::path::Path) -> bool {
+    folder.join("level.dat").exists()
+        || std::fs::read_to_string(folder.join("manifest.json"))
+            .ok()
+            .and_then(|content| parse_manifest_json(&content))
+            .and_then(|json| json.get("modules").and_then(|m| m.as_array()).cloned())
+            .is_some_and(|modules| modules.iter().any(|m| m.get("type").and_then(|t| t.as_str()) == Some("world_template")))
+}
+
+/// Replaces a pack's icon with `image_path`, scaled and cropped to the size
+/// the game expects: world_icon.jpeg for world templates, pack_icon.png for
+/// everything else. Returns the written icon path.
+#[tauri::command]
+async fn set_pack_icon(path: String, image_path: String, app: AppHandle) -> Result<String, String> {
+    ensure_not_safe_mode(&app)?;
+    let folder = PathBuf::from(&path);
+    if !is_within_configured_dirs(&folder, &app) {
+        return Err("Path is outside configured pack directories".to_string());
+    }
+    if !folder.is_dir() {
+        return Err(format!("Pack folder does not exist: {}", path));
+    }
+
+    let folder_for_task = folder.clone();
+    let icon_path = tokio::task::spawn_blocking(move || -> Result<PathBuf, String> {
+        let img = image::open(&image_path).map_err(|e| format!("Could not read image: {}", e))?;
+        let is_world = is_world_template_folder(&folder_for_task);
+        let (file_name, (width, height)) = if is_world {
+            ("world_icon.jpeg", WORLD_ICON_SIZE)
+        } else {
+            ("pack_icon.png", PACK_ICON_SIZE)
+        };
+        let resized = img.resize_to_fill(width, height, image::imageops::FilterType::Lanczos3);
+
+        // Written beside the target then renamed over it, so the folder's mtime
+        // changes and the cached icon for this pack is refreshed
+        let icon_path = folder_for_task.join(file_name);
+        let temp_path = folder_for_task.join(format!("{}.tmp", file_name));
+        let written = if is_world {
+            image::DynamicImage::ImageRgb8(resized.to_rgb8()).save_with_format(&temp_path, image::ImageFormat::Jpeg)
+        } else {
+            resized.save_with_format(&temp_path, image::ImageFormat::Png)
+        };
+        written.map_err(|e| format!("Failed to write icon: {}", e))?;
+        std::fs::rename(&temp_path, &icon_path).map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            format!("Failed to replace icon: {}", e)
+        })?;
+        Ok(icon_path)
     })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let icon_names = app.state::<AppState>().settings.read().icon_names();
+    let icon = read_pack_icon(&folder, &icon_names);
+    let _ = app.emit("pack-icon-updated", serde_json::json!({ "path": path, "icon": icon }));
+    emit_log(&app, "INFO", &format!("Set icon for {}", folder.display()));
+    Ok(icon_path.to_string_lossy().to_string())
 }
 
+/// Zips an installed or extracted pack folder back into a .mcpack for
+/// sharing. Returns the archive path.
 #[tauri::command]
-fn delete_pack(path: String, app: AppHandle) -> Result<(), String> {
-    let folder_path = std::path::Path::new(&path);
-    if !is_within_configured_dirs(folder_path, &app) {
+async fn repackage(path: String, output: String, app: AppHandle) -> Result<String, String> {
+    let folder = PathBuf::from(&path);
+    if !is_within_configured_dirs(&folder, &app) {
         return Err("Path is outside configured pack directories".to_string());
     }
-    if !folder_path.exists() {
-        return Err(format!("Path does not exist: {}", path));
+    if !folder.join("manifest.json").is_file() {
+        return Err(format!("No manifest.json in {}", path));
     }
-    
-    std::fs::remove_dir_all(folder_path)
-        .map_err(|e| format!("Failed to delete pack: {}", e))
+    let mut output = PathBuf::from(output.trim());
+    if output.as_os_str().is_empty() {
+        return Err("Output path must not be empty".to_string());
+    }
+    if output.extension().is_none() {
+        output.set_extension("mcpack");
+    }
+    let canonical_folder = folder.canonicalize().unwrap_or_else(|_| folder.clone());
+    let output_parent = output.parent()
+        .and_then(|p| p.canonicalize().ok())
+        .ok_or_else(|| format!("Output folder does not exist: {}", output.display()))?;
+    if output_parent.starts_with(&canonical_folder) {
+        return Err("Output must not be inside the pack folder".to_string());
+    }
+
+    emit_log(&app, "INFO", &format!("Repackaging '{}' to '{}'", path, output.display()));
+    let app_for_task = app.clone();
+    let output_for_task = output.clone();
+    // zip_pack_folder only learns the file count once it starts
+    let batch = once_cell::sync::OnceCell::new();
+    let progress = move |_: usize, total: usize| {
+        batch
+            .get_or_init(|| BatchProgress::new(&app_for_task, total, 25, "Zipped", "repackaging"))
+            .tick();
+    };
+    tokio::task::spawn_blocking(move || zip_pack_folder(&folder, &output_for_task, progress))
+        .await
+        .map_err(|e| format!("Repackage failed: {}", e))??;
+
+    let output_str = output.to_string_lossy().to_string();
+    emit_log(&app, "SUCCESS", &format!("Created {}", output_str));
+    Ok(output_str)
 }
 
 #[tauri::command]
-fn move_pack(path: String, destination: String, app: AppHandle) -> Result<String, String> {
+fn reclassify_pack(path: String, new_type: PackType, app: AppHandle) -> Result<String, String> {
+    ensure_not_safe_mode(&app)?;
     let source_path = std::path::Path::new(&path);
-    let dest_path = std::path::Path::new(&destination);
-    
     if !is_within_configured_dirs(source_path, &app) {
         return Err("Source path is outside configured pack directories".to_string());
     }
-    if !is_within_configured_dirs(dest_path, &app) {
+    if !source_path.is_dir() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+
+    let module_type = new_type
+        .manifest_module_type()
+        .ok_or_else(|| format!("Cannot reclassify a pack as {}", new_type))?;
+    let dest_base = get_destination_for_pack_type(new_type, app.clone())
+        .filter(|d| !d.is_empty())
+        .ok_or_else(|| format!("No destination configured for {}", new_type))?;
+    let dest_base = PathBuf::from(dest_base);
+    if !is_within_configured_dirs(&dest_base, &app) {
         return Err("Destination is outside configured pack directories".to_string());
     }
 
-    if !source_path.exists() {
-        return Err(format!("Source path does not exist: {}", path));
+    let manifest_path = source_path.join("manifest.json");
+    let original_manifest = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read manifest.json: {}", e))?;
+    let manifest = parse_manifest_json(&original_manifest)
+        .ok_or_else(|| "manifest.json could not be parsed".to_string())?;
+
+    let pack_module_types = ["data", "resources", "skin_pack", "world_template"];
+    let pack_modules = manifest.get("modules")
+        .and_then(|m| m.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|module| module.get("type")
+            .and_then(|t| t.as_str())
+            .is_some_and(|t| pack_module_types.contains(&t)))
+        .count();
+    if pack_modules == 0 {
+        return Err("manifest.json has no pack module to update".to_string());
     }
-    
+    // Only the module types are rewritten, so comments and formatting in
+    // hand-edited manifests survive. Bail out if the text doesn't line up
+    // with what was parsed rather than guess which field to change.
+    if PACK_MODULE_TYPE.find_iter(&original_manifest).count() != pack_modules {
+        return Err("manifest.json is laid out unusually; change its module type by hand".to_string());
+    }
+
     let folder_name = source_path
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("Unknown");
-    
-    let final_dest = dest_path.join(folder_name);
-    
+    let new_name = sanitize_folder_name(&format!("{}{}", strip_pack_suffix(folder_name), new_type.folder_suffix()));
+    let final_dest = dest_base.join(&new_name);
     if final_dest.exists() {
         return Err(format!("Destination already exists: {}", final_dest.display()));
     }
+
+    let content = PACK_MODULE_TYPE.replace_all(&original_manifest, |caps: &regex::Captures| {
+        format!(r#""type"{}"{}""#, &caps[1], module_type)
+    });
+    std::fs::write(&manifest_path, content.as_bytes())
+        .map_err(|e| format!("Failed to update manifest.json: {}", e))?;
+
+    std::fs::create_dir_all(&dest_base)
+        .and_then(|_| std::fs::rename(source_path, &final_dest))
+        .map_err(|e| {
+            let _ = std::fs::write(&manifest_path, &original_manifest);
+            format!("Failed to move pack: {}", e)
+        })?;
+
+    emit_log(&app, "INFO", &format!("Reclassified '{}' as {} at {}", folder_name, new_type, final_dest.display()));
+    Ok(final_dest.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn extract_files(path: String, patterns: Vec<String>, destination: String, app: AppHandle) -> Result<Vec<String>, String> {
+    if patterns.is_empty() {
+        return Err("No file patterns given".to_string());
+    }
+    let source_path = PathBuf::from(&path);
+    let dest_path = PathBuf::from(&destination);
+    if !source_path.is_file() {
+        return Err(format!("Source archive does not exist: {}", path));
+    }
+    if !is_within_configured_dirs(&dest_path, &app) {
+        return Err("Destination is outside configured pack directories".to_string());
+    }
+
+    let extracted = tokio::task::spawn_blocking(move || {
+        std::fs::create_dir_all(&dest_path).map_err(|e| format!("Failed to create directory: {}", e))?;
+        extract_matching_files(&source_path, &patterns, &dest_path)
+    })
+    .await
+    .map_err(|e| format!("Extraction failed: {}", e))??;
+
+    emit_log(&app, "INFO", &format!("Extracted {} files from {} to {}", extracted.len(), path, destination));
+    Ok(extracted)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackRename {
+    pub old_path: String,
+    pub new_path: String,
+    pub applied: bool,
+    pub error: Option<String>,
+}
+
+#[tauri::command]
+async fn normalize_pack_names(dry_run: bool, app: AppHandle) -> Result<Vec<PackRename>, String> {
+    if !dry_run {
+        ensure_not_safe_mode(&app)?;
+    }
+    let app_for_task = app.clone();
+    let renames = tokio::task::spawn_blocking(move || {
+        let mut renames = Vec::new();
+        let mut claimed: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+
+        for pack in get_installed_packs_info(&app_for_task) {
+            let folder_path = std::path::Path::new(&pack.path);
+            let base_name = read_folder_display_name(folder_path)
+                .unwrap_or_else(|| strip_pack_suffix(&pack.folder_name));
+            let canonical_name = format!("{}{}", base_name, pack.pack_type.folder_suffix());
+            if base_name.is_empty() || pack.folder_name == canonical_name {
+                continue;
+            }
+
+            let new_path = folder_path.with_file_name(&canonical_name);
+            let mut rename = PackRename {
+                old_path: pack.path.clone(),
+                new_path: new_path.to_string_lossy().to_string(),
+                applied: false,
+                error: None,
+            };
+
+            // A case-only rename resolves to the same folder on case-insensitive filesystems
+            let is_same_folder = new_path.exists()
+                && new_path.canonicalize().ok() == folder_path.canonicalize().ok();
+
+            if !is_within_configured_dirs(folder_path, &app_for_task) {
+                rename.error = Some("Path is outside configured pack directories".to_string());
+            } else if (new_path.exists() && !is_same_folder) || !claimed.insert(new_path.clone()) {
+                rename.error = Some(format!("A folder named '{}' already exists", canonical_name));
+            } else if !dry_run {
+                match std::fs::rename(folder_path, &new_path) {
+                    Ok(()) => rename.applied = true,
+                    Err(e) => rename.error = Some(format!("Failed to rename pack: {}", e)),
+                }
+            }
+
+            renames.push(rename);
+        }
+
+        renames
+    })
+    .await
+    .map_err(|e| format!("Normalize failed: {}", e))?;
+
+    let applied = renames.iter().filter(|r| r.applied).count();
+    if dry_run {
+        emit_log(&app, "INFO", &format!("[DRY RUN] {} pack folders would be renamed", renames.len()));
+    } else {
+        emit_log(&app, "INFO", &format!("Renamed {} of {} pack folders", applied, renames.len()));
+    }
+
+    Ok(renames)
+}
+
+fn regenerate_manifest_uuids(manifest_path: &std::path::Path) -> Result<(), String> {
+    let content = std::fs::read_to_string(manifest_path)
+        .map_err(|e| format!("Failed to read manifest: {}", e))?;
+    let mut json = serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{feff}'))
+        .map_err(|e| format!("Failed to parse manifest: {}", e))?;
     
-    std::fs::rename(source_path, &final_dest)
-        .map_err(|e| format!("Failed to move pack: {}", e))?;
+    if let Some(header) = json.get_mut("header").and_then(|h| h.as_object_mut()) {
+        header.insert("uuid".to_string(), serde_json::Value::String(uuid::Uuid::new_v4().to_string()));
+    } else {
+        return Err("Manifest has no header".to_string());
+    }
     
-    Ok(final_dest.to_string_lossy().to_string())
+    if let Some(modules) = json.get_mut("modules").and_then(|m| m.as_array_mut()) {
+        for module in modules {
+            if let Some(module) = module.as_object_mut() {
+                module.insert("uuid".to_string(), serde_json::Value::String(uuid::Uuid::new_v4().to_string()));
+            }
+        }
+    }
+    
+    let output = serde_json::to_string_pretty(&json).map_err(|e| e.to_string())?;
+    std::fs::write(manifest_path, output)
+        .map_err(|e| format!("Failed to write manifest: {}", e))
 }
 
 #[tauri::command]
-fn rename_pack(path: String, new_name: String, app: AppHandle) -> Result<String, String> {
-    if new_name.contains('/') || new_name.contains('\\') || new_name.contains("..") {
-        return Err("Invalid name: must not contain path separators or '..'".to_string());
+fn duplicate_pack(path: String, new_name: String, app: AppHandle) -> Result<String, String> {
+    ensure_not_safe_mode(&app)?;
+    let new_name = new_name.trim();
+    if new_name.is_empty() || new_name.contains('/') || new_name.contains('\\') || new_name.contains("..") {
+        return Err("Invalid name: must not be empty or contain path separators or '..'".to_string());
+    }
+    if new_name.chars().any(|c| matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*') || c.is_control()) {
+        return Err("Invalid name: contains characters not allowed in folder names".to_string());
     }
+    
     let folder_path = std::path::Path::new(&path);
     if !is_within_configured_dirs(folder_path, &app) {
         return Err("Path is outside configured pack directories".to_string());
     }
-    if !folder_path.exists() {
+    if !folder_path.is_dir() {
         return Err(format!("Path does not exist: {}", path));
     }
     
-    let parent = folder_path.parent()
-        .ok_or("Cannot rename root directory")?;
-    
-    let new_path = parent.join(&new_name);
+    let manifest_path = folder_path.join("manifest.json");
+    if !manifest_path.exists() {
+        return Err("Pack has no manifest.json to regenerate UUIDs in".to_string());
+    }
     
+    let parent = folder_path.parent()
+        .ok_or("Cannot duplicate root directory")?;
+    let new_path = parent.join(new_name);
     if new_path.exists() {
         return Err(format!("A folder named '{}' already exists", new_name));
     }
     
-    std::fs::rename(folder_path, &new_path)
-        .map_err(|e| format!("Failed to rename pack: {}", e))?;
+    std::fs::create_dir_all(&new_path)
+        .map_err(|e| format!("Failed to create folder: {}", e))?;
     
+    // Without fresh UUIDs Minecraft treats the copy as the same pack, so undo the copy on failure
+    let result = copy_dir_recursive(folder_path, &new_path)
+        .and_then(|_| regenerate_manifest_uuids(&new_path.join("manifest.json")));
+    if let Err(e) = result {
+        let _ = std::fs::remove_dir_all(&new_path);
+        return Err(format!("Failed to duplicate pack: {}", e));
+    }
+    // The receipt describes the original's install, not the copy's
+    let _ = std::fs::remove_file(new_path.join(RECEIPT_FILE_NAME));
+    
+    emit_log(&app, "SUCCESS", &format!("Duplicated '{}' as '{}'", path, new_path.display()));
     Ok(new_path.to_string_lossy().to_string())
 }
 
 #[tauri::command]
 fn delete_packs(paths: Vec<String>, app: AppHandle) -> Result<Vec<String>, String> {
+    ensure_not_safe_mode(&app)?;
     let mut deleted = Vec::new();
     let mut errors = Vec::new();
     
@@ -1672,9 +4881,10 @@ fn delete_packs(paths: Vec<String>, app: AppHandle) -> Result<Vec<String>, Strin
     Ok(deleted)
 }
 
-#[tauri::command]
-fn delete_source_file(path: String, app: AppHandle) -> Result<(), String> {
-    let file_path = std::path::Path::new(&path);
+/// Only pack files sitting directly in the scan folder may be deleted as sources.
+fn check_deletable_source(file_path: &std::path::Path, app: &AppHandle) -> Result<(), String> {
+    ensure_not_safe_mode(app)?;
+    let path = file_path.to_string_lossy();
     let allowed_extensions = ["mcpack", "mcaddon", "mctemplate"];
     let ext = file_path
         .extension()
@@ -1705,17 +4915,69 @@ fn delete_source_file(path: String, app: AppHandle) -> Result<(), String> {
     if parent_str != scan_str {
         return Err("File is outside the scan folder".to_string());
     }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SourceDeletionReport {
+    deleted: Vec<String>,
+    errors: Vec<String>,
+}
+
+/// Deletes the source files of successful operations once the user has confirmed.
+#[tauri::command]
+fn delete_processed_sources(operations: Vec<MoveOperation>, app: AppHandle) -> SourceDeletionReport {
+    let mut report = SourceDeletionReport { deleted: Vec::new(), errors: Vec::new() };
+    let mut seen = std::collections::HashSet::new();
+    // Multi-pack archives produce one operation per pack but share a source
+    // file; if any of those packs failed, the archive is still needed to retry
+    let failed: std::collections::HashSet<&str> = operations
+        .iter()
+        .filter(|op| !op.success)
+        .map(|op| op.source.as_str())
+        .collect();
+
+    for op in operations.iter().filter(|op| op.success && !op.reverted && !op.staged) {
+        if !seen.insert(op.source.clone()) {
+            continue;
+        }
+        if failed.contains(op.source.as_str()) {
+            report.errors.push(format!("{}: kept because another pack in it failed to install", op.source));
+            continue;
+        }
+        let file_path = std::path::Path::new(&op.source);
+        let result = check_deletable_source(file_path, &app)
+            .and_then(|_| std::fs::remove_file(file_path).map_err(|e| format!("Failed to delete file: {}", e)));
+        match result {
+            Ok(()) => {
+                emit_log(&app, "INFO", &format!("Deleted source file: {}", op.source));
+                report.deleted.push(op.source.clone());
+            }
+            Err(e) => report.errors.push(format!("{}: {}", op.source, e)),
+        }
+    }
+
+    report
+}
+
+#[tauri::command]
+fn delete_source_file(path: String, app: AppHandle) -> Result<(), String> {
+    let file_path = std::path::Path::new(&path);
+    check_deletable_source(file_path, &app)?;
     std::fs::remove_file(file_path)
         .map_err(|e| format!("Failed to delete file: {}", e))
 }
 
 #[tauri::command]
-async fn get_all_pack_icons(paths: Vec<String>) -> Result<Vec<(String, Option<String>)>, String> {
+async fn get_all_pack_icons(paths: Vec<String>, app: AppHandle) -> Result<Vec<(String, Option<String>)>, String> {
+    let icon_names = app.state::<AppState>().settings.read().icon_names();
+    let progress = BatchProgress::new(&app, paths.len(), 25, "Loaded icons", "loading-icons");
     let results: Vec<(String, Option<String>)> = tokio::task::spawn_blocking(move || {
         use rayon::prelude::*;
         paths.into_par_iter()
             .map(|path| {
-                let icon = read_pack_icon(std::path::Path::new(&path));
+                let icon = read_pack_icon(std::path::Path::new(&path), &icon_names);
+                progress.tick();
                 (path, icon)
             })
             .collect()
@@ -1724,14 +4986,179 @@ async fn get_all_pack_icons(paths: Vec<String>) -> Result<Vec<(String, Option<St
     Ok(results)
 }
 
+/// Most common color of a decoded icon as "#rrggbb". Pixels are grouped into
+/// coarse buckets and the busiest bucket's average wins, so a large flat
+/// background beats a scatter of detail colors. Transparent pixels are ignored.
+fn dominant_color(img: &image::DynamicImage) -> Option<String> {
+    let small = img.thumbnail(32, 32).to_rgba8();
+    // Bucket -> summed r, g, b and pixel count
+    let mut buckets: std::collections::HashMap<(u8, u8, u8), [u64; 4]> = std::collections::HashMap::new();
+    for pixel in small.pixels() {
+        let [r, g, b, a] = pixel.0;
+        if a < 128 {
+            continue;
+        }
+        let entry = buckets.entry((r >> 4, g >> 4, b >> 4)).or_default();
+        entry[0] += r as u64;
+        entry[1] += g as u64;
+        entry[2] += b as u64;
+        entry[3] += 1;
+    }
+    let [r, g, b, count] = buckets.into_values().max_by_key(|sums| sums[3])?;
+    Some(format!("#{:02x}{:02x}{:02x}", r / count, g / count, b / count))
+}
+
+fn read_icon_color(folder_path: &std::path::Path, icon_names: &[String]) -> Option<String> {
+    icon_names
+        .iter()
+        .map(|name| folder_path.join(name))
+        .filter(|path| path.is_file())
+        .find_map(|path| image::open(&path).ok())
+        .and_then(|img| dominant_color(&img))
+}
+
+/// Accent color per pack folder, taken from its icon. Cached with the icons.
+#[tauri::command]
+async fn pack_icon_colors(paths: Vec<String>, app: AppHandle) -> Result<Vec<(String, Option<String>)>, String> {
+    let icon_names = app.state::<AppState>().settings.read().icon_names();
+    let variant = format!("color:{}", icon_names.join(","));
+    tokio::task::spawn_blocking(move || {
+        use rayon::prelude::*;
+        paths.into_par_iter()
+            .map(|path| {
+                let folder_path = std::path::Path::new(&path);
+                let color = cached_folder_icon(folder_path, &icon_names, &variant, || read_icon_color(folder_path, &icon_names));
+                (path, color)
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackNameValidation {
+    original: String,
+    sanitized: String,
+    changed: bool,
+    issues: Vec<String>,
+}
+
+#[tauri::command]
+async fn preview_install_name(pack: PackInfo, app: AppHandle) -> Result<String, String> {
+    let settings = app.state::<AppState>().settings.read().clone();
+    tokio::task::spawn_blocking(move || install_folder_name(&settings, &pack))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn validate_pack_name(name: String) -> PackNameValidation {
+    let sanitized = sanitize_folder_name(&name);
+    PackNameValidation {
+        changed: sanitized != name,
+        issues: folder_name_issues(&name),
+        original: name,
+        sanitized,
+    }
+}
+
+#[tauri::command]
+async fn inventory_skins(app: AppHandle) -> Result<Vec<SkinEntry>, String> {
+    let skin_pack_path = app.state::<AppState>().settings.read().skin_pack_path.clone()
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| "No skin pack destination configured".to_string())?;
+
+    let root = PathBuf::from(&skin_pack_path);
+    if !root.is_dir() {
+        return Err(format!("Skin pack folder not found: {}", skin_pack_path));
+    }
+
+    let folders: Vec<PathBuf> = std::fs::read_dir(&root)
+        .map_err(|e| format!("Failed to read skin pack folder: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+
+    let skins = tokio::task::spawn_blocking(move || {
+        use rayon::prelude::*;
+        folders.par_iter()
+            .filter_map(|folder| read_pack_skins(folder))
+            .flatten()
+            .collect::<Vec<SkinEntry>>()
+    }).await.map_err(|e| e.to_string())?;
+
+    emit_log(&app, "INFO", &format!("Found {} skins across installed skin packs", skins.len()));
+    Ok(skins)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackDetails {
+    path: String,
+    exists: bool,
+    size: u64,
+    size_formatted: String,
+    icon: Option<String>,
+    uuid: Option<String>,
+    name: Option<String>,
+    version: Option<String>,
+}
+
+/// Batched get_folder_size + get_pack_icon + get_pack_info, one IPC call for a whole list.
+#[tauri::command]
+async fn get_pack_details(paths: Vec<String>, app: AppHandle) -> Result<Vec<PackDetails>, String> {
+    let icon_names = app.state::<AppState>().settings.read().icon_names();
+    tokio::task::spawn_blocking(move || {
+        use rayon::prelude::*;
+        paths.into_par_iter()
+            .map(|path| {
+                let folder_path = std::path::Path::new(&path);
+                if !folder_path.is_dir() {
+                    return PackDetails {
+                        path,
+                        exists: false,
+                        size: 0,
+                        size_formatted: format_bytes(0),
+                        icon: None,
+                        uuid: None,
+                        name: None,
+                        version: None,
+                    };
+                }
+                let PackMetadata { uuid, name, version, .. } = read_pack_metadata_fast(folder_path);
+                let size = calculate_folder_size(folder_path);
+                PackDetails {
+                    exists: true,
+                    size,
+                    size_formatted: format_bytes(size),
+                    icon: read_pack_icon(folder_path, &icon_names),
+                    uuid,
+                    name,
+                    version,
+                    path,
+                }
+            })
+            .collect()
+    }).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn clear_icon_cache(app: AppHandle) -> Result<usize, String> {
+    let removed = remove_cached_icons()?;
+    emit_log(&app, "INFO", &format!("Cleared {} cached icons", removed));
+    Ok(removed)
+}
+
 #[tauri::command]
-fn get_pack_icon(path: String) -> Option<String> {
+fn get_pack_icon(path: String, app: AppHandle) -> Option<String> {
     let folder_path = std::path::Path::new(&path);
     if !folder_path.exists() || !folder_path.is_dir() {
         return None;
     }
     
-    read_pack_icon(folder_path)
+    let icon_names = app.state::<AppState>().settings.read().icon_names();
+    read_pack_icon(folder_path, &icon_names)
 }
 
 #[tauri::command]
@@ -1740,8 +5167,15 @@ fn is_debug_mode(app: AppHandle) -> bool {
     state.debug_mode.load(std::sync::atomic::Ordering::Relaxed)
 }
 
+/// Notes in debug mode that a pack's manifest.json needed the lenient parser.
+fn log_lenient_manifest(app: &AppHandle, folder_path: &std::path::Path) {
+    if app.state::<AppState>().debug_mode.load(std::sync::atomic::Ordering::Relaxed) {
+        emit_log(app, "DEBUG", &format!("Lenient JSON parse needed for {}", folder_path.join("manifest.json").display()));
+    }
+}
+
 #[tauri::command]
-fn get_pack_info(path: String) -> Option<(String, String)> {
+fn get_pack_info(path: String, app: AppHandle) -> Option<(String, String)> {
     // Returns (uuid, name) from manifest.json if found
     let folder_path = std::path::Path::new(&path);
     if !folder_path.exists() || !folder_path.is_dir() {
@@ -1754,7 +5188,10 @@ fn get_pack_info(path: String) -> Option<(String, String)> {
     }
     
     if let Ok(content) = std::fs::read_to_string(&manifest_path) {
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
+        if let Some((json, lenient)) = parse_manifest_json_lenient(&content) {
+            if lenient {
+                log_lenient_manifest(&app, folder_path);
+            }
             let uuid = json.get("header")
                 .and_then(|h| h.get("uuid"))
                 .and_then(|u| u.as_str())
@@ -1774,6 +5211,45 @@ fn get_pack_info(path: String) -> Option<(String, String)> {
     None
 }
 
+const DEBUG_LOG_OPERATIONS: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostics {
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub config_dir: Option<String>,
+    pub debug_mode: bool,
+    pub safe_mode: bool,
+    pub minecraft_running: bool,
+    pub destinations: Vec<DestinationStatus>,
+    pub installed_packs: Vec<PackStats>,
+}
+
+/// Machine-readable counterpart of `export_debug_log`, for bug reports and
+/// the diagnostics panel.
+#[tauri::command]
+async fn get_diagnostics(app: AppHandle) -> Result<Diagnostics, String> {
+    let destinations = precheck_destinations(app.clone()).await?;
+    let installed_packs = get_installed_packs_stats(app.clone()).await?;
+    let minecraft_running = tokio::task::spawn_blocking(minecraft_process_running)
+        .await
+        .map_err(|e| format!("Process check failed: {}", e))?;
+    let safe_mode = app.state::<AppState>().settings.read().safe_mode.unwrap_or(false);
+
+    Ok(Diagnostics {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        config_dir: app_config_dir().map(|d| d.to_string_lossy().to_string()),
+        debug_mode: is_debug_mode(app.clone()),
+        safe_mode,
+        minecraft_running,
+        destinations,
+        installed_packs,
+    })
+}
+
 #[tauri::command]
 fn export_debug_log() -> Result<String, String> {
     let mut log_content = String::new();
@@ -1795,6 +5271,22 @@ fn export_debug_log() -> Result<String, String> {
     log_content.push_str("\n--- App Info ---\n");
     log_content.push_str(&format!("Version: {}\n", env!("CARGO_PKG_VERSION")));
     
+    log_content.push_str("\n--- Recent Operations ---\n");
+    for op in get_recent_operations(DEBUG_LOG_OPERATIONS) {
+        let timing = match (op.duration_ms, op.extracted_bytes) {
+            (Some(ms), Some(bytes)) => format!("{} ms, {}", ms, format_bytes(bytes)),
+            (Some(ms), None) => format!("{} ms", ms),
+            _ => "no timing".to_string(),
+        };
+        log_content.push_str(&format!(
+            "{} [{}] {} ({})\n",
+            op.timestamp.as_deref().unwrap_or("-"),
+            if op.success { "OK" } else { "FAILED" },
+            op.pack_name,
+            timing
+        ));
+    }
+    
     Ok(log_content)
 }
 
@@ -1858,32 +5350,6 @@ fn close_window(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-fn calculate_folder_size(path: &std::path::Path) -> u64 {
-    let mut size = 0;
-    let mut stack = vec![path.to_path_buf()];
-    
-    while let Some(current_path) = stack.pop() {
-        if let Ok(entries) = std::fs::read_dir(&current_path) {
-            for entry in entries.flatten() {
-                match entry.metadata() {
-                    Ok(metadata) => {
-                        if metadata.is_dir() {
-                            stack.push(entry.path());
-                        } else {
-                            size += metadata.len();
-                        }
-                    }
-                    Err(_) => {
-                        // Skip files/dirs we can't read metadata for
-                        continue;
-                    }
-                }
-            }
-        }
-    }
-    size
-}
-
 fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     if bytes == 0 {
@@ -1923,6 +5389,8 @@ fn emit_log(app: &AppHandle, level: &str, message: &str) {
         timestamp: chrono::Local::now().format("%H:%M:%S%.3f").to_string(),
         level: level.to_string(),
         message: message.to_string(),
+        pack: None,
+        pack_index: None,
     };
     let _ = app.emit("log", log);
 }
@@ -2041,9 +5509,12 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .manage(AppState {
             settings: RwLock::new(settings),
-            watching: AtomicBool::new(false),
             debug_mode: AtomicBool::new(debug_mode),
-            watch_stop_tx: parking_lot::Mutex::new(None),
+            cancel_requested: AtomicBool::new(false),
+            watchers: parking_lot::Mutex::new(std::collections::HashMap::new()),
+            watcher_events: std::sync::atomic::AtomicU64::new(0),
+            fingerprint_cache: parking_lot::Mutex::new(std::collections::HashMap::new()),
+            history: MoveHistory::default(),
         })
         .setup(move |app| {
             let icon_name = if icon_style == "default" {
@@ -2063,17 +5534,42 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             scan_packs,
             process_packs,
+            install_pack_group,
+            precheck_destinations,
             rollback_last,
+            get_recent_operations,
             get_settings,
             save_settings,
             load_settings,
+            export_settings_profile,
+            import_settings_profile,
             get_destination_for_pack_type,
             open_folder,
+            open_config_folder,
+            get_config_path,
             auto_detect_paths,
             get_premium_cache_packs,
             open_skinmaster,
             open_premium_cache,
             import_4d_skin_to_premium,
+            validate_4d_skin,
+            list_4d_skins,
+            diff_packs,
+            inventory_skins,
+            validate_pack_name,
+            reclassify_pack,
+            is_minecraft_running,
+            read_pack_readme,
+            pack_content_profile,
+            get_pack_details,
+            install_4d_to_premium,
+            classify_paths,
+            preview_install_name,
+            save_collection,
+            list_collections,
+            install_collection,
+            verify_archive,
+            undo_4d_import,
             watch_premium_cache,
             stop_watching,
             get_installed_packs_stats,
@@ -2081,16 +5577,56 @@ pub fn run() {
             launch_toolcoin,
             check_toolcoin_installed,
             delete_all_packs,
+            clean_empty_pack_folders,
             get_directory_folders,
             get_all_folder_sizes,
+            read_world_active_packs,
             get_folder_size,
             get_all_pack_icons,
             delete_pack,
             move_pack,
             rename_pack,
+            normalize_pack_names,
+            extract_files,
+            duplicate_pack,
+            find_near_duplicates,
             delete_packs,
             delete_source_file,
+            delete_processed_sources,
+            retry_failed,
+            scan_file,
+            update_size_delta,
+            set_safe_mode,
+            watch_scan_folder,
+            remap_paths,
+            find_addon_pairs,
+            commit_staged,
+            discard_staged,
+            set_pack_icon,
+            plan_import,
+            cancel_operation,
+            get_diagnostics,
+            add_tag,
+            remove_tag,
+            get_tags,
+            filter_by_tag,
+            is_pack_installed,
+            list_4d_extractions,
+            remove_4d_extractions,
+            classify_file,
+            install_and_reveal,
+            find_bloated_entries,
+            watcher_status,
+            pack_icon_colors,
+            validate_pack_json,
+            set_packs_root,
+            pack_compression_info,
+            repackage,
+            preview_delete_all,
+            list_available_themes,
+            update_counts,
             get_pack_icon,
+            clear_icon_cache,
             is_debug_mode,
             export_debug_log,
             get_pack_info,
@@ -2099,9 +5635,118 @@ pub fn run() {
             maximize_window,
             close_window,
             save_ui_scale,
+            sort_packs,
             compute_pack_status,
+            attention_summary,
             fetch_marketplace_icons,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_pack(name: &str, version: Option<&str>) -> PackInfo {
+        serde_json::from_value(serde_json::json!({
+            "path": format!("/downloads/{}.mcpack", name),
+            "name": name,
+            "pack_type": PackType::BehaviorPack,
+            "version": version,
+            "extracted": false,
+        }))
+        .unwrap()
+    }
+
+    fn installed_pack(name: &str, version: &str, folder_name: &str) -> InstalledPackInfo {
+        InstalledPackInfo {
+            uuid: None,
+            name: name.to_string(),
+            pack_type: PackType::BehaviorPack,
+            version: Some(version.to_string()),
+            path: format!("/packs/{}", folder_name),
+            folder_name: folder_name.to_string(),
+            source: None,
+        }
+    }
+
+    #[test]
+    fn same_named_installs_match_closest_version() {
+        let installed = vec![
+            installed_pack("Castle Pack", "1.0.0", "Castle Pack_BP"),
+            installed_pack("Castle Pack", "2.0.0", "Castle Pack 2_BP"),
+        ];
+        let lookup = InstalledLookup::new(&installed);
+
+        assert_eq!(lookup.find(&source_pack("Castle Pack", Some("2.1.0"))), Some(1));
+        assert_eq!(lookup.find(&source_pack("Castle Pack", Some("1.0.1"))), Some(0));
+    }
+
+    #[test]
+    fn same_named_installs_without_source_version_still_match() {
+        let installed = vec![
+            installed_pack("Castle Pack", "1.0.0", "Castle Pack_BP"),
+            installed_pack("Castle Pack", "2.0.0", "Castle Pack 2_BP"),
+        ];
+        let lookup = InstalledLookup::new(&installed);
+
+        assert!(lookup.find(&source_pack("Castle Pack", None)).is_some());
+        assert_eq!(lookup.find(&source_pack("Other Pack", Some("1.0.0"))), None);
+    }
+
+    #[tokio::test]
+    async fn four_d_folder_resolves_the_same_everywhere() {
+        let scan = std::env::temp_dir().join(format!("blocksmith-test-{}", uuid::Uuid::new_v4()));
+        let settings = Settings {
+            scan_location: Some(scan.to_string_lossy().to_string()),
+            skin_4d_subfolder_name: Some("My 4D Skins".to_string()),
+            dry_run: true,
+            ..Settings::default()
+        };
+        let expected = scan.join("My 4D Skins");
+
+        let from_command = destination_for_pack_type(&settings, PackType::SkinPack4D).map(PathBuf::from);
+        assert_eq!(from_command.as_ref(), Some(&expected));
+
+        let mover = FileMover::with_history(settings, MoveHistory::default());
+        assert_eq!(mover.get_destination_path(PackType::SkinPack4D, Some(&scan)).as_ref(), Some(&expected));
+
+        let mut pack = source_pack("Dragon Skins", None);
+        pack.pack_type = PackType::SkinPack4D;
+        pack.path = scan.join("Dragon Skins.mcpack").to_string_lossy().to_string();
+        // With and without a scan dir, process_pack lands in the same folder
+        for scan_dir in [Some(&scan), None] {
+            let op = mover.process_pack(&pack, scan_dir, None).await;
+            assert!(op.success, "{:?}", op.error);
+            assert_eq!(PathBuf::from(&op.destination).parent(), Some(expected.as_path()));
+        }
+    }
+
+    #[test]
+    fn undated_packs_sort_last_in_both_directions() {
+        let dir = std::env::temp_dir().join(format!("blocksmith-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dated = |name: &str, secs: u64| {
+            let path = dir.join(format!("{}.mcpack", name));
+            std::fs::write(&path, b"").unwrap();
+            std::fs::File::options().write(true).open(&path).unwrap()
+                .set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs)).unwrap();
+            let mut pack = source_pack(name, None);
+            pack.path = path.to_string_lossy().to_string();
+            pack
+        };
+        let older = dated("Older", 1_000_000);
+        let newer = dated("Newer", 2_000_000);
+        let names = |packs: &[PackInfo]| packs.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+
+        let mut packs = vec![source_pack("Undated", None), newer.clone(), older.clone()];
+        sort_pack_list(&mut packs, "date", false);
+        assert_eq!(names(&packs), ["Older", "Newer", "Undated"]);
+
+        sort_pack_list(&mut packs, "date", true);
+        assert_eq!(names(&packs), ["Newer", "Older", "Undated"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DriveKind {
+    Fixed,
+    Removable,
+    Network,
+    Unknown,
+}
+
+/// Best-effort guess at what kind of drive `path` lives on. Returns Unknown
+/// whenever the platform won't tell us.
+pub fn classify_drive(path: &Path) -> DriveKind {
+    platform::classify(path)
+}
+
+/// Drive letter ("C:") or mount point `path` lives on, so paths sharing free
+/// space can be grouped together.
+pub fn drive_root(path: &Path) -> Option<String> {
+    platform::root(path)
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::DriveKind;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::{Component, Path, Prefix};
+
+    const DRIVE_REMOVABLE: u32 = 2;
+    const DRIVE_FIXED: u32 = 3;
+    const DRIVE_REMOTE: u32 = 4;
+    const DRIVE_CDROM: u32 = 5;
+    const DRIVE_RAMDISK: u32 = 6;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetDriveTypeW(root_path_name: *const u16) -> u32;
+    }
+
+    pub fn root(path: &Path) -> Option<String> {
+        match path.components().next()? {
+            Component::Prefix(prefix) => Some(prefix.as_os_str().to_string_lossy().to_uppercase()),
+            _ => None,
+        }
+    }
+
+    pub fn classify(path: &Path) -> DriveKind {
+        let root = match path.components().next() {
+            Some(Component::Prefix(prefix)) => match prefix.kind() {
+                Prefix::UNC(..) | Prefix::VerbatimUNC(..) => return DriveKind::Network,
+                Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => format!("{}:\\", letter as char),
+                _ => return DriveKind::Unknown,
+            },
+            _ => return DriveKind::Unknown,
+        };
+
+        let wide: Vec<u16> = std::ffi::OsStr::new(&root).encode_wide().chain(Some(0)).collect();
+        // SAFETY: `wide` is a NUL-terminated UTF-16 string that outlives the call
+        match unsafe { GetDriveTypeW(wide.as_ptr()) } {
+            DRIVE_FIXED | DRIVE_RAMDISK => DriveKind::Fixed,
+            DRIVE_REMOVABLE | DRIVE_CDROM => DriveKind::Removable,
+            DRIVE_REMOTE => DriveKind::Network,
+            _ => DriveKind::Unknown,
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::DriveKind;
+    use std::path::Path;
+
+    const NETWORK_FS_TYPES: &[&str] = &["nfs", "nfs4", "cifs", "smbfs", "smb3", "afpfs", "fuse.sshfs", "9p", "webdav", "davfs"];
+    const REMOVABLE_MOUNT_ROOTS: &[&str] = &["/media/", "/run/media/", "/mnt/", "/Volumes/"];
+
+    /// (mount point, filesystem type) pairs from /proc/self/mounts or `mount`.
+    fn mounts() -> Vec<(String, String)> {
+        if let Ok(content) = std::fs::read_to_string("/proc/self/mounts") {
+            return content
+                .lines()
+                .filter_map(|line| {
+                    let mut fields = line.split_whitespace();
+                    let _device = fields.next()?;
+                    let mount_point = fields.next()?.replace("\\040", " ");
+                    let fs_type = fields.next()?.to_string();
+                    Some((mount_point, fs_type))
+                })
+                .collect();
+        }
+
+        // macOS: "/dev/disk2s1 on /Volumes/USB (msdos, local, nodev)"
+        let Ok(output) = std::process::Command::new("mount").output() else {
+            return Vec::new();
+        };
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (_, rest) = line.split_once(" on ")?;
+                let (mount_point, details) = rest.rsplit_once(" (")?;
+                let fs_type = details.split(',').next()?.trim().to_string();
+                Some((mount_point.to_string(), fs_type))
+            })
+            .collect()
+    }
+
+    fn mount_of(path: &Path) -> Option<(String, String)> {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        mounts()
+            .into_iter()
+            .filter(|(mount_point, _)| canonical.starts_with(mount_point))
+            .max_by_key(|(mount_point, _)| mount_point.len())
+    }
+
+    pub fn root(path: &Path) -> Option<String> {
+        mount_of(path).map(|(mount_point, _)| mount_point)
+    }
+
+    pub fn classify(path: &Path) -> DriveKind {
+        let Some((mount_point, fs_type)) = mount_of(path) else {
+            return DriveKind::Unknown;
+        };
+        if NETWORK_FS_TYPES.contains(&fs_type.as_str()) {
+            DriveKind::Network
+        } else if mount_point != "/" && REMOVABLE_MOUNT_ROOTS.iter().any(|root| mount_point.starts_with(root)) {
+            DriveKind::Removable
+        } else {
+            DriveKind::Fixed
+        }
+    }
+}
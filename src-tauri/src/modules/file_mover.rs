@@ -1,11 +1,15 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 use std::sync::Arc;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use tokio::sync::mpsc;
 use serde::{Deserialize, Serialize};
-use super::pack_type::{PackInfo, PackType, Settings};
-use super::pack_detector::extract_pack_to_destination;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use super::pack_type::{InstallReceipt, PackInfo, PackType, Settings};
+use super::folder_size::calculate_folder_size;
+use super::skin_pack_4d::collect_relative_files;
+use super::pack_detector::{compute_file_hash, extract_pack_excluding, ExtractExclusions, extract_uuid, extract_version, parse_manifest_json, read_archive_manifest_version, sanitize_folder_name};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MoveOperation {
@@ -18,6 +22,18 @@ pub struct MoveOperation {
     pub is_template_update: Option<bool>,
     pub skin_pack_4d_path: Option<String>,
     pub deleted_old_path: Option<String>,
+    pub archived_old_path: Option<String>,
+    pub uuid: Option<String>,
+    pub source_hash: Option<String>,
+    pub note: Option<String>,
+    pub timestamp: Option<String>,
+    #[serde(default)]
+    pub reverted: bool,
+    pub duration_ms: Option<u64>,
+    pub extracted_bytes: Option<u64>,
+    /// Extracted into Settings.staging_dir; not installed until commit_staged
+    #[serde(default)]
+    pub staged: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -25,12 +41,232 @@ pub struct LogEntry {
     pub timestamp: String,
     pub level: String,
     pub message: String,
+    pub pack: Option<String>,
+    pub pack_index: Option<usize>,
 }
 
 pub type LogSender = mpsc::UnboundedSender<LogEntry>;
 pub type MoveHistory = Arc<RwLock<Vec<MoveOperation>>>;
 
-fn strip_pack_suffix(name: &str) -> String {
+const MAX_PERSISTED_HISTORY: usize = 1000;
+
+static VERSION_TAIL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\s*[\(\[]?v?\d+(\.\d+)+[\)\]]?\s*$").unwrap());
+
+fn history_file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("blocksmith").join("history.json"))
+}
+
+pub fn load_history_from_file() -> Vec<MoveOperation> {
+    history_file_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|content| serde_json::from_str::<Vec<MoveOperation>>(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn append_history_to_file(ops: &[MoveOperation]) -> Result<(), String> {
+    if ops.is_empty() {
+        return Ok(());
+    }
+    
+    let path = history_file_path()
+        .ok_or_else(|| "Could not determine config directory".to_string())?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    
+    let mut history = load_history_from_file();
+    history.extend(ops.iter().cloned());
+    if history.len() > MAX_PERSISTED_HISTORY {
+        let excess = history.len() - MAX_PERSISTED_HISTORY;
+        history.drain(..excess);
+    }
+    
+    let content = serde_json::to_string_pretty(&history).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+pub const RECEIPT_FILE_NAME: &str = ".blocksmith.json";
+pub const STAGED_FILE_NAME: &str = ".blocksmith_staged.json";
+
+/// Written into a staged pack folder so commit_staged knows where it belongs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StagedPack {
+    pack: PackInfo,
+    output_name: String,
+    dest_base: String,
+    type_base: String,
+}
+
+fn read_staged(staged_path: &Path) -> Result<StagedPack, String> {
+    let content = fs::read_to_string(staged_path.join(STAGED_FILE_NAME))
+        .map_err(|_| format!("Not a staged pack: {}", staged_path.display()))?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid staging record: {}", e))
+}
+
+/// Folder a staged pack will be installed into, as recorded when it was staged.
+pub fn staged_dest_base(staged_path: &Path) -> Result<PathBuf, String> {
+    read_staged(staged_path).map(|staged| PathBuf::from(staged.dest_base))
+}
+
+/// Renames `from` to `to`, falling back to copy-then-delete when they sit on
+/// different drives. A failed copy leaves `from` untouched.
+fn move_dir(from: &Path, to: &Path) -> Result<(), String> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    let copied = collect_relative_files(from).iter().try_for_each(|rel| {
+        let target = to.join(rel);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(from.join(rel), target).map(|_| ())
+    });
+    if let Err(e) = copied {
+        let _ = fs::remove_dir_all(to);
+        return Err(e.to_string());
+    }
+    fs::remove_dir_all(from).map_err(|e| e.to_string())
+}
+
+pub fn read_receipt(pack_dir: &Path) -> Option<InstallReceipt> {
+    fs::read_to_string(pack_dir.join(RECEIPT_FILE_NAME))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Confirms an extraction left a readable manifest.json carrying the source
+/// pack's UUID. Packs scanned without a UUID (e.g. skin packs) aren't checked.
+fn verify_extracted_manifest(dest: &Path, expected_uuid: Option<&str>) -> Result<(), String> {
+    let Some(expected) = expected_uuid else {
+        return Ok(());
+    };
+    let manifest_path = dest.join("manifest.json");
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|_| "Extraction check failed: manifest.json is missing at the destination".to_string())?;
+    let json = parse_manifest_json(&content)
+        .ok_or_else(|| "Extraction check failed: manifest.json at the destination is unreadable".to_string())?;
+    match extract_uuid(&json) {
+        Some(uuid) if uuid.eq_ignore_ascii_case(expected) => Ok(()),
+        Some(uuid) => Err(format!("Extraction check failed: manifest UUID {} does not match source {}", uuid, expected)),
+        None => Err("Extraction check failed: manifest.json at the destination has no UUID".to_string()),
+    }
+}
+
+fn write_receipt(pack_dir: &Path, receipt: &InstallReceipt) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(receipt).map_err(|e| e.to_string())?;
+    fs::write(pack_dir.join(RECEIPT_FILE_NAME), content).map_err(|e| e.to_string())
+}
+
+/// Zips an extracted pack folder into `output` with deflate, leaving out
+/// Blocksmith's own marker files. Writes to a temp file first so a failed
+/// run never leaves a truncated archive behind. `progress` gets (done, total).
+pub fn zip_pack_folder(folder: &Path, output: &Path, progress: impl Fn(usize, usize)) -> Result<(), String> {
+    let files: Vec<String> = collect_relative_files(folder)
+        .into_iter()
+        .filter(|rel| rel != RECEIPT_FILE_NAME && rel != STAGED_FILE_NAME)
+        .collect();
+    let total = files.len();
+
+    let temp_path = output.with_extension("mcpack.tmp");
+    let result = (|| -> Result<(), String> {
+        let file = fs::File::create(&temp_path).map_err(|e| format!("Failed to create archive: {}", e))?;
+        let mut writer = zip::ZipWriter::new(std::io::BufWriter::new(file));
+        for (idx, rel) in files.iter().enumerate() {
+            let path = folder.join(rel);
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated)
+                .large_file(size >= u32::MAX as u64);
+            writer.start_file(rel.as_str(), options).map_err(|e| format!("Failed to add {}: {}", rel, e))?;
+            let mut source = fs::File::open(&path).map_err(|e| format!("Failed to read {}: {}", rel, e))?;
+            std::io::copy(&mut source, &mut writer).map_err(|e| format!("Failed to add {}: {}", rel, e))?;
+            progress(idx + 1, total);
+        }
+        writer.finish().map_err(|e| format!("Failed to finish archive: {}", e))?;
+        fs::rename(&temp_path, output).map_err(|e| format!("Failed to write {}: {}", output.display(), e))
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// World save data left untouched by "preserve-saves" template updates.
+const WORLD_SAVE_ENTRIES: &[&str] = &["db", "level.dat", "level.dat_old"];
+
+/// Moves existing world save data out of `destination` into a sibling folder
+/// so re-extraction can't overwrite it. Returns the stash folder, if anything was moved.
+fn stash_world_saves(destination: &Path) -> Result<Option<PathBuf>, String> {
+    let folder_name = destination
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid template folder".to_string())?;
+    let stash = destination.with_file_name(format!(".{}.blocksmith-saves", folder_name));
+
+    let mut moved: Vec<&str> = Vec::new();
+    for entry in WORLD_SAVE_ENTRIES {
+        let path = destination.join(entry);
+        if !path.exists() {
+            continue;
+        }
+        let result = fs::create_dir_all(&stash)
+            .and_then(|_| fs::rename(&path, stash.join(entry)))
+            .map_err(|e| format!("Failed to set aside {}: {}", entry, e));
+        if let Err(e) = result {
+            // Put back what was already moved so nothing is stranded in the stash
+            for done in &moved {
+                let _ = fs::rename(stash.join(done), destination.join(done));
+            }
+            let _ = fs::remove_dir(&stash);
+            return Err(e);
+        }
+        moved.push(entry);
+    }
+
+    Ok(if moved.is_empty() { None } else { Some(stash) })
+}
+
+fn restore_world_saves(stash: &Path, destination: &Path) -> Result<(), String> {
+    fs::create_dir_all(destination).map_err(|e| e.to_string())?;
+
+    for entry in WORLD_SAVE_ENTRIES {
+        let saved = stash.join(entry);
+        if !saved.exists() {
+            continue;
+        }
+        let target = destination.join(entry);
+        if target.is_dir() {
+            fs::remove_dir_all(&target).map_err(|e| e.to_string())?;
+        } else if target.exists() {
+            fs::remove_file(&target).map_err(|e| e.to_string())?;
+        }
+        fs::rename(&saved, &target).map_err(|e| format!("Failed to restore {}: {}", entry, e))?;
+    }
+
+    fs::remove_dir_all(stash).map_err(|e| e.to_string())
+}
+
+/// Flags the persisted entry for a rolled-back operation so the activity list can show it.
+pub fn mark_reverted_in_history_file(op: &MoveOperation) -> Result<(), String> {
+    let path = history_file_path()
+        .ok_or_else(|| "Could not determine config directory".to_string())?;
+    let mut history = load_history_from_file();
+    
+    let entry = history.iter_mut().rev().find(|h| {
+        !h.reverted && h.destination == op.destination && h.timestamp == op.timestamp
+    });
+    match entry {
+        Some(entry) => entry.reverted = true,
+        None => return Ok(()),
+    }
+    
+    let content = serde_json::to_string_pretty(&history).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+pub fn strip_pack_suffix(name: &str) -> String {
     let suffixes = [" (ADDON)", "(ADDON)", " (RESOURCE)", "(RESOURCE)", " (SKIN)", "(SKIN)", " (TEMPLATE)", "(TEMPLATE)", " (MASHUP)", "(MASHUP)"];
     let mut result = name.to_string();
     for suffix in &suffixes {
@@ -42,56 +278,198 @@ fn strip_pack_suffix(name: &str) -> String {
     result.trim().to_string()
 }
 
-fn find_old_pack_path(dest_base: &PathBuf, pack_name: &str, pack_type: PackType) -> Option<PathBuf> {
-    if !dest_base.exists() {
+/// Drops a trailing version such as " v1.2.3" or " (1.2)" so folders named by
+/// an install_name_template still match the plain pack name.
+fn strip_version_tail(name: &str) -> String {
+    VERSION_TAIL.replace(name, "").trim().to_string()
+}
+
+/// Resolves an entry to its real directory path, following a symlink once.
+/// Returns None for files and broken links.
+pub fn resolve_pack_dir(entry_path: &Path) -> Option<PathBuf> {
+    let metadata = fs::metadata(entry_path).ok()?;
+    if !metadata.is_dir() {
         return None;
     }
-    
-    let base_name = strip_pack_suffix(pack_name).to_lowercase();
-    
-    if let Ok(entries) = fs::read_dir(dest_base) {
+    Some(entry_path.canonicalize().unwrap_or_else(|_| entry_path.to_path_buf()))
+}
+
+const MAX_CATEGORY_DEPTH: usize = 3;
+
+/// Pack folders under a destination folder, descending into category folders
+/// (see PackInfo.category): folders without a manifest.json whose
+/// subfolders hold packs.
+pub fn pack_dirs_in(dir: &Path) -> Vec<PathBuf> {
+    fn walk(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else { return };
         for entry in entries.flatten() {
             let entry_path = entry.path();
-            if entry_path.is_dir() {
-                if let Some(folder_name) = entry_path.file_name().and_then(|n| n.to_str()) {
-                    let folder_base = strip_pack_suffix(folder_name).to_lowercase();
-                    
-                    if folder_base == base_name {
-                        let type_suffix = match pack_type {
-                            PackType::BehaviorPack => " (ADDON)",
-                            PackType::ResourcePack => " (RESOURCE)",
-                            PackType::SkinPack => " (SKIN)",
-                            PackType::SkinPack4D => "",
-                            PackType::WorldTemplate => " (TEMPLATE)",
-                            PackType::MashupPack => " (MASHUP)",
-                            PackType::Unknown => "",
-                        };
-                        
-                        let expected_name = format!("{}{}", strip_pack_suffix(pack_name), type_suffix);
-                        if folder_name != expected_name {
-                            return Some(entry_path);
-                        }
-                    }
+            if resolve_pack_dir(&entry_path).is_none() {
+                continue;
+            }
+            if depth < MAX_CATEGORY_DEPTH && !entry_path.join("manifest.json").exists() {
+                let mut nested = Vec::new();
+                walk(&entry_path, depth + 1, &mut nested);
+                if nested.iter().any(|p| p.join("manifest.json").exists()) {
+                    out.extend(nested);
+                    continue;
                 }
             }
+            out.push(entry_path);
         }
     }
+    let mut out = Vec::new();
+    walk(dir, 0, &mut out);
+    out
+}
+
+fn find_old_pack_path(type_base: &Path, pack_name: &str, new_destination: &Path) -> Option<PathBuf> {
+    if !type_base.exists() {
+        return None;
+    }
+    
+    let base_name = strip_version_tail(&strip_pack_suffix(pack_name)).to_lowercase();
+    
+    // The old version may sit at the type root or in any category folder
+    pack_dirs_in(type_base).into_iter().find(|entry_path| {
+        entry_path.file_name().and_then(|n| n.to_str()).is_some_and(|folder_name| {
+            strip_version_tail(&strip_pack_suffix(folder_name)).to_lowercase() == base_name
+                && entry_path != new_destination
+        })
+    })
+}
+
+const OLD_VERSIONS_DIR: &str = ".blocksmith_versions";
+
+/// Where Settings.keep_old_versions parks superseded packs: next to the
+/// destination folder rather than inside it, so the game never loads them.
+fn old_versions_dir(dest_base: &Path) -> PathBuf {
+    let type_folder = dest_base.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    dest_base.parent().unwrap_or(dest_base).join(OLD_VERSIONS_DIR).join(type_folder)
+}
+
+fn archived_base_name(folder_name: &str) -> String {
+    let name = match folder_name.rfind(" [old ") {
+        Some(idx) => &folder_name[..idx],
+        None => folder_name,
+    };
+    strip_version_tail(&strip_pack_suffix(name)).to_lowercase()
+}
+
+/// Moves an old pack folder into the versions archive as "<name> [old <version>]"
+/// and prunes that pack's archived copies down to `keep`, oldest first.
+fn archive_old_version(old_path: &Path, dest_base: &Path, keep: usize) -> Result<PathBuf, String> {
+    let folder_name = old_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| "Invalid old version folder name".to_string())?;
+    let archive_dir = old_versions_dir(dest_base);
+    fs::create_dir_all(&archive_dir).map_err(|e| format!("Failed to create versions folder: {}", e))?;
+    
+    let stamp = chrono::Local::now().format("%Y-%m-%d %H%M%S").to_string();
+    let version = fs::read_to_string(old_path.join("manifest.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|json| extract_version(&json));
+    let mut target = archive_dir.join(format!("{} [old {}]", folder_name, version.as_deref().unwrap_or(&stamp)));
+    if target.exists() {
+        target = archive_dir.join(format!("{} [old {} {}]", folder_name, version.as_deref().unwrap_or_default(), stamp));
+    }
+    fs::rename(old_path, &target).map_err(|e| e.to_string())?;
+    
+    let base_name = archived_base_name(folder_name);
+    let mut archived: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(&archive_dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .filter(|entry| archived_base_name(&entry.file_name().to_string_lossy()) == base_name)
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    archived.sort_by_key(|(modified, _)| *modified);
+    let excess = archived.len().saturating_sub(keep);
+    for (_, path) in archived.into_iter().take(excess) {
+        let _ = fs::remove_dir_all(path);
+    }
     
-    None
+    Ok(target)
+}
+
+/// Renders Settings.install_name_template. Returns None when the template is
+/// unset or uses a token this pack has no value for, so the caller can fall
+/// back to the plain "{name}{type_suffix}" folder name.
+fn render_install_name(template: Option<&str>, pack: &PackInfo, version: Option<&str>) -> Option<String> {
+    let template = template.map(str::trim).filter(|t| !t.is_empty())?;
+    if (template.contains("{version}") && version.is_none())
+        || (template.contains("{uuid}") && pack.uuid.is_none())
+    {
+        return None;
+    }
+    
+    let rendered = template
+        .replace("{name}", pack.install_base_name())
+        .replace("{version}", version.unwrap_or(""))
+        .replace("{type_suffix}", pack.pack_type.folder_suffix())
+        .replace("{uuid}", pack.uuid.as_deref().unwrap_or(""));
+    
+    if rendered.trim().is_empty() { None } else { Some(sanitize_folder_name(&rendered)) }
+}
+
+/// Folder name process_pack installs `pack` under. Only reads the archive when
+/// the install template needs a {version} the scan didn't find.
+pub fn install_folder_name(settings: &Settings, pack: &PackInfo) -> String {
+    let template = settings.install_name_template.as_deref();
+    let version = if template.is_some_and(|t| t.contains("{version}")) && pack.version.is_none() {
+        read_archive_manifest_version(Path::new(&pack.path), pack.subfolder.as_deref())
+    } else {
+        pack.version.clone()
+    };
+    render_install_name(template, pack, version.as_deref())
+        .unwrap_or_else(|| sanitize_folder_name(&format!("{}{}", pack.install_base_name(), pack.pack_type.folder_suffix())))
+}
+
+/// Relative folder for PackInfo.category. Accepts "/" or "\\" separators and
+/// rejects "." / ".." segments or drive prefixes that could escape dest_base.
+pub fn category_subpath(category: &str) -> Result<PathBuf, String> {
+    let mut path = PathBuf::new();
+    for segment in category.split(['/', '\\']).map(str::trim).filter(|s| !s.is_empty()) {
+        if segment == "." || segment == ".." || segment.contains(':') {
+            return Err(format!("Invalid category '{}': path traversal is not allowed", category));
+        }
+        path.push(sanitize_folder_name(segment));
+    }
+    Ok(path)
+}
+
+/// Tags log lines with the pack they belong to. With Settings.ordered_logs the
+/// lines are held back and sent as one block once the pack finishes, so
+/// concurrent extractions don't interleave in the log panel.
+struct PackLog {
+    name: String,
+    index: Option<usize>,
+    buffer: Option<Mutex<Vec<LogEntry>>>,
 }
 
 pub struct FileMover {
     settings: Settings,
     log_tx: Option<LogSender>,
     history: MoveHistory,
+    // Operations this mover added to the shared history, persisted by the caller
+    recorded: Mutex<Vec<MoveOperation>>,
+    prior_history: Vec<MoveOperation>,
 }
 
 impl FileMover {
-    pub fn new(settings: Settings) -> Self {
+    /// `history` is shared between movers so a later rollback_last can undo
+    /// what an earlier process_packs call installed.
+    pub fn with_history(settings: Settings, history: MoveHistory) -> Self {
         Self {
             settings,
             log_tx: None,
-            history: Arc::new(RwLock::new(Vec::new())),
+            history,
+            recorded: Mutex::new(Vec::new()),
+            prior_history: Vec::new(),
         }
     }
     
@@ -99,21 +477,67 @@ impl FileMover {
         self.log_tx = Some(tx);
     }
     
+    pub fn set_prior_history(&mut self, history: Vec<MoveOperation>) {
+        self.prior_history = history;
+    }
+    
+    pub fn recorded_operations(&self) -> Vec<MoveOperation> {
+        self.recorded.lock().clone()
+    }
+    
+    fn find_identical_install(&self, pack: &PackInfo, destination: &str, source_hash: &str) -> Option<&MoveOperation> {
+        self.prior_history.iter().rev().find(|op| {
+            op.success
+                && op.source_hash.as_deref() == Some(source_hash)
+                && match (&op.uuid, &pack.uuid) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => op.destination == destination,
+                }
+        })
+    }
+    
     fn log(&self, level: &str, message: &str) {
+        self.send_log(LogEntry {
+            timestamp: chrono::Local::now().format("%H:%M:%S%.3f").to_string(),
+            level: level.to_string(),
+            message: message.to_string(),
+            pack: None,
+            pack_index: None,
+        });
+    }
+    
+    fn send_log(&self, entry: LogEntry) {
         if let Some(tx) = &self.log_tx {
-            let _ = tx.send(LogEntry {
-                timestamp: chrono::Local::now().format("%H:%M:%S%.3f").to_string(),
-                level: level.to_string(),
-                message: message.to_string(),
-            });
+            let _ = tx.send(entry);
+        }
+    }
+    
+    fn pack_log(&self, ctx: &PackLog, level: &str, message: &str) {
+        let prefix = match ctx.index {
+            Some(idx) => format!("[#{} {}]", idx + 1, ctx.name),
+            None => format!("[{}]", ctx.name),
+        };
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().format("%H:%M:%S%.3f").to_string(),
+            level: level.to_string(),
+            message: format!("{} {}", prefix, message),
+            pack: Some(ctx.name.clone()),
+            pack_index: ctx.index,
+        };
+        match &ctx.buffer {
+            Some(buffer) => buffer.lock().push(entry),
+            None => self.send_log(entry),
         }
     }
     
     pub fn get_destination_path(&self, pack_type: PackType, scan_dir: Option<&PathBuf>) -> Option<PathBuf> {
         match pack_type {
             PackType::SkinPack4D => {
+                if let Some(path) = self.settings.skin_pack_4d_path.as_ref().filter(|p| !p.is_empty()) {
+                    return Some(PathBuf::from(path));
+                }
                 if let Some(scan) = scan_dir {
-                    return Some(scan.join("4D Skin Packs"));
+                    return Some(scan.join(self.settings.skin_4d_subfolder()));
                 }
                 return None;
             }
@@ -132,20 +556,48 @@ impl FileMover {
         }
     }
     
-    pub async fn process_pack(&self, pack: &PackInfo, scan_dir: Option<&PathBuf>) -> MoveOperation {
+    /// `index` is the pack's position in the batch, used to tell its log lines
+    /// apart from concurrent extractions.
+    pub async fn process_pack(&self, pack: &PackInfo, scan_dir: Option<&PathBuf>, index: Option<usize>) -> MoveOperation {
+        let ctx = PackLog {
+            name: pack.name.clone(),
+            index,
+            buffer: if self.settings.ordered_logs.unwrap_or(false) {
+                Some(Mutex::new(Vec::new()))
+            } else {
+                None
+            },
+        };
+        
+        let mut result = self.process_pack_logged(pack, scan_dir, &ctx).await;
+        if result.timestamp.is_none() {
+            result.timestamp = Some(chrono::Local::now().to_rfc3339());
+        }
+        
+        if let Some(buffer) = ctx.buffer {
+            for entry in buffer.into_inner() {
+                self.send_log(entry);
+            }
+        }
+        result
+    }
+    
+    async fn process_pack_logged(&self, pack: &PackInfo, scan_dir: Option<&PathBuf>, ctx: &PackLog) -> MoveOperation {
         let source = PathBuf::from(&pack.path);
         
-        let (dest_base, is_4d_skin_pack) = if pack.pack_type == PackType::SkinPack4D {
-            let parent_dir = source.parent()
-                .map(|p| p.to_path_buf())
-                .unwrap_or_else(|| PathBuf::from("."));
-            let four_d_dir = parent_dir.join("4D Skin Packs");
+        let (type_base, is_4d_skin_pack) = if pack.pack_type == PackType::SkinPack4D {
+            let four_d_dir = self.get_destination_path(pack.pack_type, scan_dir).unwrap_or_else(|| {
+                source.parent()
+                    .map(|p| p.to_path_buf())
+                    .unwrap_or_else(|| PathBuf::from("."))
+                    .join(self.settings.skin_4d_subfolder())
+            });
             (four_d_dir, true)
         } else {
             match self.get_destination_path(pack.pack_type, scan_dir) {
                 Some(p) => (p, false),
                 None => {
-                    self.log("ERROR", &format!("No destination path configured for {}", pack.pack_type));
+                    self.pack_log(ctx, "ERROR", &format!("No destination path configured for {}", pack.pack_type));
                     return MoveOperation {
                         source: pack.path.clone(),
                         destination: String::new(),
@@ -156,37 +608,92 @@ impl FileMover {
                         is_template_update: None,
                         skin_pack_4d_path: None,
                         deleted_old_path: None,
+                        archived_old_path: None,
+                        uuid: pack.uuid.clone(),
+                        source_hash: None,
+                        note: None,
+                        timestamp: None,
+                        reverted: false,
+                        duration_ms: None,
+                        extracted_bytes: None,
+                        staged: false,
                     };
                 }
             }
         };
         
-        let type_suffix = match pack.pack_type {
-            PackType::BehaviorPack => " (ADDON)",
-            PackType::ResourcePack => " (RESOURCE)",
-            PackType::SkinPack => " (SKIN)",
-            PackType::SkinPack4D => "",
-            PackType::WorldTemplate => " (TEMPLATE)",
-            PackType::MashupPack => " (MASHUP)",
-            PackType::Unknown => "",
+        let dest_base = match pack.category.as_deref().map(category_subpath) {
+            Some(Ok(sub)) => type_base.join(sub),
+            Some(Err(e)) => {
+                self.pack_log(ctx, "ERROR", &e);
+                return MoveOperation {
+                    source: pack.path.clone(),
+                    destination: String::new(),
+                    pack_name: pack.name.clone(),
+                    pack_type: pack.pack_type,
+                    success: false,
+                    error: Some(e),
+                    is_template_update: None,
+                    skin_pack_4d_path: None,
+                    deleted_old_path: None,
+                    archived_old_path: None,
+                    uuid: pack.uuid.clone(),
+                    source_hash: None,
+                    note: None,
+                    timestamp: None,
+                    reverted: false,
+                    duration_ms: None,
+                    extracted_bytes: None,
+                    staged: false,
+                };
+            }
+            None => type_base.clone(),
         };
         
-        let output_name = format!("{}{}", pack.name, type_suffix);
+        let output_name = {
+            let settings = self.settings.clone();
+            let pack_for_name = pack.clone();
+            tokio::task::spawn_blocking(move || install_folder_name(&settings, &pack_for_name))
+                .await
+                .unwrap_or_else(|_| sanitize_folder_name(&format!("{}{}", pack.install_base_name(), pack.pack_type.folder_suffix())))
+        };
         let destination = dest_base.join(&output_name);
         
         let is_template_update = (pack.pack_type == PackType::WorldTemplate || pack.pack_type == PackType::MashupPack) 
             && destination.exists();
         
+        let preserve_saves = is_template_update
+            && self.settings.template_update_mode.as_deref() == Some("preserve-saves");
+        
         let old_pack_path = if !is_4d_skin_pack && pack.is_update.unwrap_or(false) {
-            find_old_pack_path(&dest_base, &pack.name, pack.pack_type)
+            find_old_pack_path(&type_base, pack.install_base_name(), &destination)
         } else {
             None
         };
         
+        let keep_old_versions = self.settings.keep_old_versions.filter(|n| *n > 0);
+        
+        if let Some(staging_dir) = self.settings.staging_dir.as_deref().filter(|d| !d.trim().is_empty()) {
+            let staged = StagedPack {
+                pack: pack.clone(),
+                output_name,
+                dest_base: dest_base.to_string_lossy().to_string(),
+                type_base: type_base.to_string_lossy().to_string(),
+            };
+            return self.stage_pack(staged, Path::new(staging_dir), ctx).await;
+        }
+        
         if self.settings.dry_run {
-            self.log("INFO", &format!("[DRY RUN] Would extract '{}' to '{}'", pack.name, destination.display()));
+            self.pack_log(ctx, "INFO", &format!("[DRY RUN] Would extract '{}' to '{}'", pack.name, destination.display()));
             if let Some(ref old_path) = old_pack_path {
-                self.log("INFO", &format!("[DRY RUN] Would delete old version at '{}'", old_path.display()));
+                if keep_old_versions.is_some() {
+                    self.pack_log(ctx, "INFO", &format!("[DRY RUN] Would archive old version at '{}'", old_path.display()));
+                } else {
+                    self.pack_log(ctx, "INFO", &format!("[DRY RUN] Would delete old version at '{}'", old_path.display()));
+                }
+            }
+            if preserve_saves {
+                self.pack_log(ctx, "INFO", "[DRY RUN] Would keep existing world save data (level.dat, db)");
             }
             return MoveOperation {
                 source: pack.path.clone(),
@@ -198,21 +705,113 @@ impl FileMover {
                 is_template_update: if is_template_update { Some(true) } else { None },
                 skin_pack_4d_path: if is_4d_skin_pack { Some(destination.to_string_lossy().to_string()) } else { None },
                 deleted_old_path: old_pack_path.map(|p| p.to_string_lossy().to_string()),
+                archived_old_path: None,
+                uuid: pack.uuid.clone(),
+                source_hash: None,
+                note: None,
+                timestamp: None,
+                reverted: false,
+                duration_ms: None,
+                extracted_bytes: None,
+                staged: false,
             };
         }
         
+        // Only skip_identical reads the hash, so don't pay for it otherwise
+        let skip_identical = self.settings.skip_identical.unwrap_or(false);
+        let source_hash = if skip_identical {
+            let source_for_hash = source.clone();
+            match tokio::task::spawn_blocking(move || compute_file_hash(&source_for_hash)).await {
+                Ok(Ok(hash)) => Some(hash),
+                _ => None,
+            }
+        } else {
+            None
+        };
+        
+        if skip_identical && destination.exists() {
+            let destination_str = destination.to_string_lossy().to_string();
+            if let Some(hash) = source_hash.as_deref() {
+                if self.find_identical_install(pack, &destination_str, hash).is_some() {
+                    self.pack_log(ctx, "INFO", &format!("Skipping '{}': already up to date", pack.name));
+                    return MoveOperation {
+                        source: pack.path.clone(),
+                        destination: destination_str,
+                        pack_name: output_name,
+                        pack_type: pack.pack_type,
+                        success: true,
+                        error: None,
+                        is_template_update: None,
+                        skin_pack_4d_path: None,
+                        deleted_old_path: None,
+                        archived_old_path: None,
+                        uuid: pack.uuid.clone(),
+                        source_hash,
+                        note: Some("Already up to date".to_string()),
+                        timestamp: None,
+                        reverted: false,
+                        duration_ms: None,
+                        extracted_bytes: None,
+                        staged: false,
+                    };
+                }
+            }
+        }
+        
         if pack.pack_type == PackType::SkinPack4D {
-            self.log("INFO", "4D Skin Pack will be extracted for use with SkinMaster");
+            self.pack_log(ctx, "INFO", "4D Skin Pack will be extracted for use with SkinMaster");
         }
         
+        let mut archived_old_path = None;
         if let Some(ref old_path) = old_pack_path {
-            self.log("INFO", &format!("Deleting old version at '{}'", old_path.display()));
-            if let Err(e) = fs::remove_dir_all(old_path) {
-                self.log("WARN", &format!("Failed to delete old version: {}", e));
+            if let Some(keep) = keep_old_versions {
+                match archive_old_version(old_path, &type_base, keep) {
+                    Ok(archived) => {
+                        self.pack_log(ctx, "INFO", &format!("Archived old version to '{}'", archived.display()));
+                        archived_old_path = Some(archived.to_string_lossy().to_string());
+                    }
+                    Err(e) => self.pack_log(ctx, "WARN", &format!("Failed to archive old version: {}", e)),
+                }
+            } else {
+                self.pack_log(ctx, "INFO", &format!("Deleting old version at '{}'", old_path.display()));
+                if let Err(e) = fs::remove_dir_all(old_path) {
+                    self.pack_log(ctx, "WARN", &format!("Failed to delete old version: {}", e));
+                }
             }
         }
         
-        self.log("INFO", &format!("Extracting '{}' to '{}'", pack.name, destination.display()));
+        let saves_stash = if preserve_saves {
+            match stash_world_saves(&destination) {
+                Ok(stash) => stash,
+                Err(e) => {
+                    self.pack_log(ctx, "ERROR", &format!("Failed to preserve world save data: {}", e));
+                    return MoveOperation {
+                        source: pack.path.clone(),
+                        destination: destination.to_string_lossy().to_string(),
+                        pack_name: output_name,
+                        pack_type: pack.pack_type,
+                        success: false,
+                        error: Some(e),
+                        is_template_update: Some(true),
+                        skin_pack_4d_path: None,
+                        deleted_old_path: None,
+                        archived_old_path: None,
+                        uuid: pack.uuid.clone(),
+                        source_hash,
+                        note: None,
+                        timestamp: None,
+                        reverted: false,
+                        duration_ms: None,
+                        extracted_bytes: None,
+                        staged: false,
+                    };
+                }
+            }
+        } else {
+            None
+        };
+        
+        self.pack_log(ctx, "INFO", &format!("Extracting '{}' to '{}'", pack.name, destination.display()));
         
         let source_clone = source.clone();
         let dest_base_clone = dest_base.clone();
@@ -220,30 +819,91 @@ impl FileMover {
         let subfolder = pack.subfolder.clone();
         let output_name_for_extract = output_name.clone();
         let old_pack_path_clone = old_pack_path.clone();
+        let exclude_globs = self.settings.extract_exclude_globs.clone();
         
+        let destination_existed = destination.exists();
+        let started = std::time::Instant::now();
         let result = tokio::task::spawn_blocking(move || {
-            extract_pack_to_destination(
+            extract_pack_excluding(
                 &source_clone, 
                 &dest_base_clone, 
                 pack_type_for_extract, 
                 subfolder.as_deref(),
                 Some(&output_name_for_extract),
+                &exclude_globs,
             )
         }).await;
+        let duration_ms = started.elapsed().as_millis() as u64;
         
         let result = match result {
-            Ok(r) => r,
+            Ok(Ok((dest_path, exclusions))) => {
+                self.log_exclusions(ctx, &exclusions);
+                Ok(dest_path)
+            }
+            Ok(Err(e)) => Err(e),
             Err(e) => Err(e.to_string()),
         };
         
+        // Put the saves back even when extraction failed, so they are never lost
+        let saves_restored = match saves_stash {
+            Some(ref stash) => match restore_world_saves(stash, &destination) {
+                Ok(()) => true,
+                Err(e) => {
+                    self.pack_log(ctx, "ERROR", &format!(
+                        "Failed to restore world save data, it is kept at '{}': {}", stash.display(), e
+                    ));
+                    false
+                }
+            },
+            None => false,
+        };
+        
+        let result = match result {
+            Ok(dest_path) if self.settings.verify_after_extract.unwrap_or(true) => {
+                match verify_extracted_manifest(Path::new(&dest_path), pack.uuid.as_deref()) {
+                    Ok(()) => Ok(dest_path),
+                    Err(e) => {
+                        // Only clean up folders this run created; an existing install stays put
+                        if !destination_existed {
+                            if let Err(remove_err) = fs::remove_dir_all(&dest_path) {
+                                self.pack_log(ctx, "WARN", &format!("Failed to remove broken extraction: {}", remove_err));
+                            }
+                        }
+                        Err(e)
+                    }
+                }
+            }
+            other => other,
+        };
+        
         match result {
             Ok(dest_path) => {
-                self.log("SUCCESS", &format!("Successfully extracted '{}' to '{}'", pack.name, dest_path));
-                if is_template_update {
-                    self.log("WARN", "World template updated - existing worlds may need manual update");
+                let extracted_bytes = {
+                    let dest = PathBuf::from(&dest_path);
+                    tokio::task::spawn_blocking(move || calculate_folder_size(&dest)).await.ok()
+                };
+                self.pack_log(ctx, "SUCCESS", &format!(
+                    "Successfully extracted '{}' to '{}' in {} ms", pack.name, dest_path, duration_ms
+                ));
+                if saves_restored {
+                    self.pack_log(ctx, "INFO", "World template updated - existing world save data was kept");
+                } else if is_template_update {
+                    self.pack_log(ctx, "WARN", "World template updated - existing worlds may need manual update");
                 }
                 if is_4d_skin_pack {
-                    self.log("INFO", &format!("4D Skin Pack extracted. Use this path with SkinMaster: {}", dest_path));
+                    self.pack_log(ctx, "INFO", &format!("4D Skin Pack extracted. Use this path with SkinMaster: {}", dest_path));
+                }
+                if self.settings.write_receipt.unwrap_or(false) {
+                    let receipt = InstallReceipt {
+                        source: pack.path.clone(),
+                        source_hash: source_hash.clone(),
+                        installed_at: chrono::Local::now().to_rfc3339(),
+                        pack_type: pack.pack_type,
+                        uuid: pack.uuid.clone(),
+                    };
+                    if let Err(e) = write_receipt(Path::new(&dest_path), &receipt) {
+                        self.pack_log(ctx, "WARN", &format!("Failed to write install receipt: {}", e));
+                    }
                 }
                 let op = MoveOperation {
                     source: pack.path.clone(),
@@ -255,12 +915,22 @@ impl FileMover {
                     is_template_update: if is_template_update { Some(true) } else { None },
                     skin_pack_4d_path: if is_4d_skin_pack { Some(dest_path) } else { None },
                     deleted_old_path: old_pack_path_clone.map(|p| p.to_string_lossy().to_string()),
+                    archived_old_path,
+                    uuid: pack.uuid.clone(),
+                    source_hash,
+                    note: if saves_restored { Some("World save data preserved".to_string()) } else { None },
+                    timestamp: Some(chrono::Local::now().to_rfc3339()),
+                    reverted: false,
+                    duration_ms: Some(duration_ms),
+                    extracted_bytes,
+                    staged: false,
                 };
                 self.history.write().push(op.clone());
+                self.recorded.lock().push(op.clone());
                 op
             }
             Err(e) => {
-                self.log("ERROR", &format!("Failed to extract '{}': {}", pack.name, e));
+                self.pack_log(ctx, "ERROR", &format!("Failed to extract '{}': {}", pack.name, e));
                 MoveOperation {
                     source: pack.path.clone(),
                     destination: destination.to_string_lossy().to_string(),
@@ -271,25 +941,266 @@ impl FileMover {
                     is_template_update: None,
                     skin_pack_4d_path: None,
                     deleted_old_path: None,
+                    archived_old_path: None,
+                    uuid: pack.uuid.clone(),
+                    source_hash,
+                    note: None,
+                    timestamp: None,
+                    reverted: false,
+                    duration_ms: Some(duration_ms),
+                    extracted_bytes: None,
+                    staged: false,
                 }
             }
         }
     }
     
-    pub async fn rollback_last(&self) -> Option<MoveOperation> {
-        let op = {
-            let mut history = self.history.write();
-            history.pop()
-        }?;
+    fn log_exclusions(&self, ctx: &PackLog, exclusions: &ExtractExclusions) {
+        if exclusions.files > 0 {
+            self.pack_log(ctx, "INFO", &format!(
+                "Excluded {} files ({} KB) matching extract_exclude_globs",
+                exclusions.files,
+                exclusions.bytes / 1024
+            ));
+        }
+        if !exclusions.protected.is_empty() {
+            self.pack_log(ctx, "WARN", &format!(
+                "Refused to exclude required files: {}",
+                exclusions.protected.join(", ")
+            ));
+        }
+    }
+    
+    async fn stage_pack(&self, staged: StagedPack, staging_dir: &Path, ctx: &PackLog) -> MoveOperation {
+        let pack = &staged.pack;
+        let staged_path = staging_dir.join(&staged.output_name);
+        let mut op = MoveOperation {
+            source: pack.path.clone(),
+            destination: staged_path.to_string_lossy().to_string(),
+            pack_name: staged.output_name.clone(),
+            pack_type: pack.pack_type,
+            success: true,
+            error: None,
+            is_template_update: None,
+            skin_pack_4d_path: None,
+            deleted_old_path: None,
+            archived_old_path: None,
+            uuid: pack.uuid.clone(),
+            source_hash: None,
+            note: Some("Staged - commit to install".to_string()),
+            timestamp: None,
+            reverted: false,
+            duration_ms: None,
+            extracted_bytes: None,
+            staged: true,
+        };
+        
+        if self.settings.dry_run {
+            self.pack_log(ctx, "INFO", &format!("[DRY RUN] Would stage '{}' in '{}'", pack.name, staged_path.display()));
+            return op;
+        }
+        
+        // A previous staged copy of the same pack is replaced
+        if staged_path.exists() {
+            if let Err(e) = fs::remove_dir_all(&staged_path) {
+                self.pack_log(ctx, "WARN", &format!("Failed to clear previous staged copy: {}", e));
+            }
+        }
+        
+        self.pack_log(ctx, "INFO", &format!("Staging '{}' in '{}'", pack.name, staged_path.display()));
+        
+        let source = PathBuf::from(&pack.path);
+        let staging_dir_clone = staging_dir.to_path_buf();
+        let pack_type = pack.pack_type;
+        let subfolder = pack.subfolder.clone();
+        let output_name = staged.output_name.clone();
+        let started = std::time::Instant::now();
+        let exclude_globs = self.settings.extract_exclude_globs.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            extract_pack_excluding(&source, &staging_dir_clone, pack_type, subfolder.as_deref(), Some(&output_name), &exclude_globs)
+        })
+        .await
+        .unwrap_or_else(|e| Err(e.to_string()))
+        .map(|(dest_path, exclusions)| {
+            self.log_exclusions(ctx, &exclusions);
+            dest_path
+        });
+        op.duration_ms = Some(started.elapsed().as_millis() as u64);
+        
+        let result = result.and_then(|dest_path| {
+            let record = serde_json::to_string_pretty(&staged).map_err(|e| e.to_string())?;
+            fs::write(Path::new(&dest_path).join(STAGED_FILE_NAME), record)
+                .map_err(|e| format!("Failed to write staging record: {}", e))?;
+            Ok(dest_path)
+        });
+        
+        match result {
+            Ok(dest_path) => {
+                self.pack_log(ctx, "SUCCESS", &format!("Staged '{}' in '{}'", pack.name, dest_path));
+                op.extracted_bytes = Some(calculate_folder_size(Path::new(&dest_path)));
+                op.timestamp = Some(chrono::Local::now().to_rfc3339());
+            }
+            Err(e) => {
+                self.pack_log(ctx, "ERROR", &format!("Failed to stage '{}': {}", pack.name, e));
+                op.success = false;
+                op.error = Some(e);
+                op.note = None;
+            }
+        }
+        op
+    }
+    
+    /// Moves a staged pack folder into the destination recorded when it was
+    /// staged, retiring the installed old version the same way process_pack does.
+    /// The caller is expected to have checked the recorded dest_base.
+    pub async fn commit_staged(&self, staged_path: &Path) -> Result<MoveOperation, String> {
+        let staged = read_staged(staged_path)?;
+        let pack = &staged.pack;
+        let dest_base = PathBuf::from(&staged.dest_base);
+        let type_base = PathBuf::from(&staged.type_base);
+        if !dest_base.starts_with(&type_base)
+            || dest_base.components().any(|c| c == std::path::Component::ParentDir)
+            || staged.output_name.contains(['/', '\\'])
+        {
+            return Err("Invalid staging record: destination is outside the pack folder".to_string());
+        }
+        let destination = dest_base.join(&staged.output_name);
+        
+        let old_pack_path = if pack.pack_type != PackType::SkinPack4D && pack.is_update.unwrap_or(false) {
+            find_old_pack_path(&type_base, pack.install_base_name(), &destination)
+        } else {
+            None
+        };
+        // A folder already at the destination is replaced like an old version
+        let replaced_path = old_pack_path.or_else(|| destination.exists().then(|| destination.clone()));
+        
+        if self.settings.dry_run {
+            self.log("INFO", &format!("[DRY RUN] Would install staged '{}' to '{}'", pack.name, destination.display()));
+            if let Some(ref old_path) = replaced_path {
+                self.log("INFO", &format!("[DRY RUN] Would replace old version at '{}'", old_path.display()));
+            }
+            return Ok(MoveOperation {
+                source: pack.path.clone(),
+                destination: destination.to_string_lossy().to_string(),
+                pack_name: staged.output_name.clone(),
+                pack_type: pack.pack_type,
+                success: true,
+                error: None,
+                is_template_update: None,
+                skin_pack_4d_path: None,
+                deleted_old_path: replaced_path.map(|p| p.to_string_lossy().to_string()),
+                archived_old_path: None,
+                uuid: pack.uuid.clone(),
+                source_hash: None,
+                note: None,
+                timestamp: None,
+                reverted: false,
+                duration_ms: None,
+                extracted_bytes: None,
+                staged: true,
+            });
+        }
+        
+        fs::create_dir_all(&dest_base).map_err(|e| format!("Failed to create destination: {}", e))?;
+        
+        // Bring the staged folder onto the destination drive before touching
+        // the installed version, so a failed cross-drive move loses nothing
+        let incoming = dest_base.join(format!(".{}.blocksmith-incoming", staged.output_name));
+        if incoming.exists() {
+            return Err(format!("A previous commit left '{}' behind; move or delete it first", incoming.display()));
+        }
+        move_dir(staged_path, &incoming).map_err(|e| format!("Failed to move staged pack: {}", e))?;
+        let put_back = |e: String| match move_dir(&incoming, staged_path) {
+            Ok(()) => e,
+            Err(_) => format!("{} (the staged pack is at '{}')", e, incoming.display()),
+        };
+        
+        let is_template_update = matches!(pack.pack_type, PackType::WorldTemplate | PackType::MashupPack)
+            && replaced_path.as_ref() == Some(&destination);
+        let saves_stash = if is_template_update && self.settings.template_update_mode.as_deref() == Some("preserve-saves") {
+            stash_world_saves(&destination).map_err(put_back)?
+        } else {
+            None
+        };
+        
+        let mut archived_old_path = None;
+        if let Some(ref old_path) = replaced_path {
+            let retired = match self.settings.keep_old_versions.filter(|n| *n > 0) {
+                Some(keep) => archive_old_version(old_path, &type_base, keep)
+                    .map(|archived| {
+                        self.log("INFO", &format!("Archived old version to '{}'", archived.display()));
+                        archived_old_path = Some(archived.to_string_lossy().to_string());
+                    })
+                    .map_err(|e| format!("Failed to archive old version: {}", e)),
+                None => {
+                    self.log("INFO", &format!("Deleting old version at '{}'", old_path.display()));
+                    fs::remove_dir_all(old_path).map_err(|e| format!("Failed to delete old version: {}", e))
+                }
+            };
+            if let Err(e) = retired {
+                if let Some(ref stash) = saves_stash {
+                    let _ = restore_world_saves(stash, &destination);
+                }
+                return Err(put_back(e));
+            }
+        }
+        
+        fs::rename(&incoming, &destination).map_err(|e| {
+            format!("Failed to move staged pack into place, it is kept at '{}': {}", incoming.display(), e)
+        })?;
+        let _ = fs::remove_file(destination.join(STAGED_FILE_NAME));
+        if let Some(ref stash) = saves_stash {
+            match restore_world_saves(stash, &destination) {
+                Ok(()) => self.log("INFO", "World template updated - existing world save data was kept"),
+                Err(e) => self.log("ERROR", &format!(
+                    "Failed to restore world save data, it is kept at '{}': {}", stash.display(), e
+                )),
+            }
+        }
+        self.log("SUCCESS", &format!("Installed staged '{}' to '{}'", pack.name, destination.display()));
         
+        let destination_str = destination.to_string_lossy().to_string();
+        let op = MoveOperation {
+            source: pack.path.clone(),
+            destination: destination_str.clone(),
+            pack_name: staged.output_name.clone(),
+            pack_type: pack.pack_type,
+            success: true,
+            error: None,
+            is_template_update: is_template_update.then_some(true),
+            skin_pack_4d_path: if pack.pack_type == PackType::SkinPack4D { Some(destination_str) } else { None },
+            deleted_old_path: replaced_path.map(|p| p.to_string_lossy().to_string()),
+            archived_old_path,
+            uuid: pack.uuid.clone(),
+            source_hash: None,
+            note: Some("Committed from staging".to_string()),
+            timestamp: Some(chrono::Local::now().to_rfc3339()),
+            reverted: false,
+            duration_ms: None,
+            extracted_bytes: Some(calculate_folder_size(&destination)),
+            staged: false,
+        };
+        self.history.write().push(op.clone());
+        self.recorded.lock().push(op.clone());
+        Ok(op)
+    }
+    
+    pub async fn rollback_last(&self) -> Option<MoveOperation> {
+        // The history is shared with every other mover, so a dry run must
+        // leave the entry in place
         if self.settings.dry_run {
+            let op = self.history.read().last().cloned()?;
             self.log("INFO", &format!("[DRY RUN] Would rollback '{}'", op.pack_name));
             return Some(op);
         }
         
+        let mut op = self.history.write().pop()?;
+        
         self.log("INFO", &format!("Rolling back '{}'", op.pack_name));
         
         let source = PathBuf::from(&op.destination);
+        let archived = op.archived_old_path.clone().map(PathBuf::from);
+        let original = op.deleted_old_path.clone().map(PathBuf::from);
         
         let result = tokio::task::spawn_blocking(move || {
             if !source.exists() {
@@ -298,22 +1209,111 @@ impl FileMover {
             
             fs::remove_dir_all(&source).map_err(|e| e.to_string())?;
             
-            Ok::<(), String>(())
+            // Put the archived previous version back where it was installed
+            match (archived, original) {
+                (Some(archived), Some(original)) if archived.exists() && !original.exists() => {
+                    fs::rename(&archived, &original)
+                        .map(|_| Some(original))
+                        .map_err(|e| format!("Removed new version but failed to restore old version: {}", e))
+                }
+                _ => Ok(None),
+            }
         }).await;
         
         match result {
-            Ok(Ok(())) => {
+            Ok(Ok(restored)) => {
+                if let Some(restored) = restored {
+                    self.log("INFO", &format!("Restored previous version to '{}'", restored.display()));
+                }
                 self.log("SUCCESS", &format!("Successfully rolled back '{}'", op.pack_name));
+                op.reverted = true;
                 Some(op)
             }
             Ok(Err(e)) => {
                 self.log("ERROR", &format!("Failed to rollback '{}': {}", op.pack_name, e));
+                self.history.write().push(op);
                 None
             }
             Err(e) => {
                 self.log("ERROR", &format!("Failed to rollback '{}': {}", op.pack_name, e));
+                self.history.write().push(op);
                 None
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// A temp folder holding a behavior pack archive and an empty destination.
+    fn setup() -> (PathBuf, Settings, PackInfo) {
+        let root = std::env::temp_dir().join(format!("blocksmith-test-{}", uuid::Uuid::new_v4()));
+        let dest = root.join("behavior_packs");
+        fs::create_dir_all(&dest).unwrap();
+
+        let archive = root.join("Castle Pack.mcpack");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        writer.start_file("manifest.json", zip::write::SimpleFileOptions::default()).unwrap();
+        writer
+            .write_all(br#"{ "header": { "name": "Castle Pack", "version": [1, 0, 0] }, "modules": [{ "type": "data" }] }"#)
+            .unwrap();
+        writer.finish().unwrap();
+
+        let settings = Settings {
+            behavior_pack_path: Some(dest.to_string_lossy().to_string()),
+            ..Settings::default()
+        };
+        let pack: PackInfo = serde_json::from_value(serde_json::json!({
+            "path": archive.to_string_lossy(),
+            "name": "Castle Pack",
+            "pack_type": PackType::BehaviorPack,
+            "extracted": false,
+        }))
+        .unwrap();
+        (root, settings, pack)
+    }
+
+    #[tokio::test]
+    async fn second_mover_rolls_back_first_movers_install() {
+        let (root, settings, pack) = setup();
+        let history = MoveHistory::default();
+
+        let installer = FileMover::with_history(settings.clone(), history.clone());
+        let op = installer.process_pack(&pack, None, None).await;
+        assert!(op.success, "{:?}", op.error);
+        assert!(Path::new(&op.destination).is_dir());
+
+        let rollback = FileMover::with_history(settings, history.clone());
+        let reverted = rollback.rollback_last().await.expect("nothing to roll back");
+        assert!(reverted.reverted);
+        assert!(!Path::new(&op.destination).exists());
+        assert!(history.read().is_empty());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[tokio::test]
+    async fn dry_run_and_failed_rollbacks_keep_the_history_entry() {
+        let (root, settings, pack) = setup();
+        let history = MoveHistory::default();
+
+        let installer = FileMover::with_history(settings.clone(), history.clone());
+        let op = installer.process_pack(&pack, None, None).await;
+        assert!(op.success, "{:?}", op.error);
+
+        let dry_run = FileMover::with_history(Settings { dry_run: true, ..settings.clone() }, history.clone());
+        assert!(dry_run.rollback_last().await.is_some());
+        assert_eq!(history.read().len(), 1);
+
+        // Deleting the install out from under the rollback makes it fail
+        fs::remove_dir_all(&op.destination).unwrap();
+        let rollback = FileMover::with_history(settings, history.clone());
+        assert!(rollback.rollback_last().await.is_none());
+        assert_eq!(history.read().len(), 1);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
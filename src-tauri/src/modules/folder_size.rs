@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use super::file_mover::RECEIPT_FILE_NAME;
+
+/// Total size of the files under `path`, leaving out Blocksmith's install
+/// receipt. Directory symlinks are followed, each real folder counted once so
+/// a link cycle can't loop forever.
+pub fn calculate_folder_size(path: &Path) -> u64 {
+    let mut size = 0;
+    let mut visited = HashSet::new();
+    let mut stack = vec![path.to_path_buf()];
+
+    while let Some(current_path) = stack.pop() {
+        let canonical = current_path.canonicalize().unwrap_or_else(|_| current_path.clone());
+        if !visited.insert(canonical) {
+            continue;
+        }
+
+        if let Ok(entries) = fs::read_dir(&current_path) {
+            for entry in entries.flatten() {
+                let is_symlink = entry.file_type().map(|t| t.is_symlink()).unwrap_or(false);
+                let metadata = if is_symlink {
+                    fs::metadata(entry.path())
+                } else {
+                    entry.metadata()
+                };
+                match metadata {
+                    Ok(metadata) => {
+                        if metadata.is_dir() {
+                            stack.push(entry.path());
+                        } else if entry.file_name() != RECEIPT_FILE_NAME {
+                            size += metadata.len();
+                        }
+                    }
+                    Err(_) => {
+                        // Skip files/dirs we can't read metadata for (including broken links)
+                        continue;
+                    }
+                }
+            }
+        }
+    }
+    size
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_referential_symlink_is_counted_once() {
+        let root = std::env::temp_dir().join(format!("blocksmith-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(root.join("textures")).unwrap();
+        fs::write(root.join("manifest.json"), [0u8; 10]).unwrap();
+        fs::write(root.join("textures").join("icon.png"), [0u8; 32]).unwrap();
+        std::os::unix::fs::symlink(&root, root.join("textures").join("loop")).unwrap();
+
+        assert_eq!(calculate_folder_size(&root), 42);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
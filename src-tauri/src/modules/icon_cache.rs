@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SWEEP_MARKER: &str = ".last_sweep";
+const SWEEP_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedIcon {
+    source: String,
+    #[serde(default)]
+    variant: String,
+    /// Modification times the icon was computed from; a mismatch means the
+    /// entry is superseded and gets overwritten in place.
+    #[serde(default)]
+    stamp: String,
+    icon: Option<String>,
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("blocksmith").join("icon_cache"))
+}
+
+fn cache_key(source: &str, variant: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(source.as_bytes());
+    hasher.update([0]);
+    hasher.update(variant.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn mtime_nanos(path: &Path) -> Option<u128> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+/// Returns the encoded icon for `source`, reusing a cached copy while the
+/// source's mtime is unchanged. `variant` separates icons taken from
+/// different places in the same source, such as archive subfolders.
+pub fn cached_icon(source: &Path, variant: &str, compute: impl FnOnce() -> Option<String>) -> Option<String> {
+    match mtime_nanos(source) {
+        Some(nanos) => cached_with_stamp(source, variant, nanos.to_string(), compute),
+        None => compute(),
+    }
+}
+
+/// Like `cached_icon`, but for pack folders: the entry is keyed on the icon
+/// files themselves, since replacing a file inside a folder doesn't reliably
+/// touch the folder's own mtime.
+pub fn cached_folder_icon(
+    folder: &Path,
+    icon_names: &[String],
+    variant: &str,
+    compute: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    let stamp = icon_names
+        .iter()
+        .map(|name| match mtime_nanos(&folder.join(name)) {
+            Some(nanos) => format!("{}={}", name, nanos),
+            None => format!("{}=-", name),
+        })
+        .collect::<Vec<_>>()
+        .join(";");
+    cached_with_stamp(folder, variant, stamp, compute)
+}
+
+fn cached_with_stamp(
+    source: &Path,
+    variant: &str,
+    stamp: String,
+    compute: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    let source = source.to_string_lossy().to_string();
+    let entry_path = match cache_dir() {
+        Some(dir) => dir.join(format!("{}.json", cache_key(&source, variant))),
+        None => return compute(),
+    };
+
+    if let Some(cached) = fs::read_to_string(&entry_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<CachedIcon>(&content).ok())
+        .filter(|cached| cached.stamp == stamp)
+    {
+        return cached.icon;
+    }
+
+    let icon = compute();
+    let entry = CachedIcon {
+        source,
+        variant: variant.to_string(),
+        stamp,
+        icon: icon.clone(),
+    };
+    if let (Some(parent), Ok(content)) = (entry_path.parent(), serde_json::to_string(&entry)) {
+        let _ = fs::create_dir_all(parent);
+        let _ = fs::write(&entry_path, content);
+    }
+    icon
+}
+
+pub fn remove_cached_icons() -> Result<usize, String> {
+    let dir = match cache_dir() {
+        Some(d) if d.exists() => d,
+        _ => return Ok(0),
+    };
+
+    let mut removed = 0;
+    for entry in fs::read_dir(&dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("json") && fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Drops cached icons whose source file or folder no longer exists, along
+/// with entries left over from the old mtime-in-the-name key scheme. Runs at
+/// most once a day; returns the number of entries removed.
+pub fn evict_stale_icons() -> usize {
+    let dir = match cache_dir() {
+        Some(d) if d.exists() => d,
+        _ => return 0,
+    };
+
+    let marker = dir.join(SWEEP_MARKER);
+    let recently_swept = fs::metadata(&marker)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .is_some_and(|age| age < SWEEP_INTERVAL);
+    if recently_swept {
+        return 0;
+    }
+
+    let mut removed = 0;
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let current = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<CachedIcon>(&content).ok())
+                .is_some_and(|cached| {
+                    Path::new(&cached.source).exists()
+                        && path.file_stem().and_then(|s| s.to_str())
+                            == Some(cache_key(&cached.source, &cached.variant).as_str())
+                });
+            if !current && fs::remove_file(&path).is_ok() {
+                removed += 1;
+            }
+        }
+    }
+
+    let _ = fs::write(&marker, b"");
+    removed
+}
@@ -0,0 +1,72 @@
+use std::path::{Path, PathBuf};
+
+/// `path` in a form the file APIs accept whatever its length. On Windows an
+/// absolute path at or past MAX_PATH gets the `\\?\` prefix; a relative one
+/// that long can't, so it's reported as an error instead.
+pub fn long_path(path: &Path) -> Result<PathBuf, String> {
+    platform::long_path(path)
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::ffi::OsString;
+    use std::path::{Component, Path, PathBuf, Prefix};
+
+    /// Longest path the classic Win32 file APIs accept, drive letter included.
+    const MAX_PATH: usize = 260;
+
+    fn too_long(path: &Path) -> String {
+        format!(
+            "Path is too long for Windows ({} characters, limit {}): {}. Choose a shorter destination folder.",
+            path.as_os_str().len(),
+            MAX_PATH,
+            path.display()
+        )
+    }
+
+    pub fn long_path(path: &Path) -> Result<PathBuf, String> {
+        if path.as_os_str().len() < MAX_PATH {
+            return Ok(path.to_path_buf());
+        }
+
+        let mut components = path.components();
+        let mut extended = match components.next() {
+            Some(Component::Prefix(prefix)) => match prefix.kind() {
+                Prefix::Verbatim(_) | Prefix::VerbatimDisk(_) | Prefix::VerbatimUNC(..) => return Ok(path.to_path_buf()),
+                Prefix::Disk(letter) => PathBuf::from(format!(r"\\?\{}:\", letter as char)),
+                Prefix::UNC(server, share) => {
+                    let mut root = OsString::from(r"\\?\UNC\");
+                    root.push(server);
+                    root.push(r"\");
+                    root.push(share);
+                    root.push(r"\");
+                    PathBuf::from(root)
+                }
+                _ => return Err(too_long(path)),
+            },
+            _ => return Err(too_long(path)),
+        };
+
+        // Verbatim paths skip normalisation, so rebuild with backslashes and
+        // resolve `.`/`..` here; archive entries use forward slashes.
+        for component in components {
+            match component {
+                Component::Normal(part) => extended.push(part),
+                Component::ParentDir => {
+                    extended.pop();
+                }
+                _ => {}
+            }
+        }
+        Ok(extended)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use std::path::{Path, PathBuf};
+
+    pub fn long_path(path: &Path) -> Result<PathBuf, String> {
+        Ok(path.to_path_buf())
+    }
+}
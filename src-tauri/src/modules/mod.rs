@@ -1,7 +1,25 @@
 pub mod pack_type;
 pub mod pack_detector;
 pub mod file_mover;
+pub mod skin_inventory;
+pub mod skin_pack_4d;
+pub mod pack_diff;
+pub mod icon_cache;
+pub mod pack_fingerprint;
+pub mod pack_profile;
+pub mod drive_kind;
+pub mod long_path;
+pub mod folder_size;
 
-pub use pack_type::{PackInfo, PackType, Settings};
-pub use pack_detector::scan_single_pack;
-pub use file_mover::{FileMover, LogEntry, MoveOperation};
+pub use pack_type::{attention_message_for, AttentionReason, PackInfo, PackType, Settings};
+pub use pack_detector::{verify_archive_entries, ArchiveHealth, classify_archive, compression_info, CompressionInfo, json_error, read_archive_json_files, JsonError, encode_icon_data, extract_authors, extract_dependencies, extract_description, extract_format_version, extract_matching_files, extract_pack_to_destination, folder_name_issues, find_pack_readme, list_pack_files, sanitize_folder_name, has_zip_magic, parse_manifest_json, parse_manifest_json_lenient, read_folder_display_name, scan_single_pack};
+pub use file_mover::{FileMover, LogEntry, MoveHistory, MoveOperation, install_folder_name, load_history_from_file, append_history_to_file, mark_reverted_in_history_file, read_receipt, pack_dirs_in, resolve_pack_dir, staged_dest_base, strip_pack_suffix, zip_pack_folder, RECEIPT_FILE_NAME, STAGED_FILE_NAME};
+pub use folder_size::calculate_folder_size;
+pub use skin_pack_4d::{collect_relative_files, find_skins_json, validate_4d_folder, Validation4D};
+pub use pack_diff::{diff_pack_paths, PackDiff};
+pub use icon_cache::{cached_folder_icon, evict_stale_icons, remove_cached_icons};
+pub use pack_fingerprint::{fingerprint_folder, fingerprint_similarity};
+pub use skin_inventory::{read_pack_skins, SkinEntry};
+pub use pack_profile::{profile_pack_contents, ContentProfile};
+pub use drive_kind::{classify_drive, drive_root, DriveKind};
+pub use long_path::long_path;
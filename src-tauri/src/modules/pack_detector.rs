@@ -1,12 +1,49 @@
-use super::pack_type::{PackInfo, PackType};
+use super::icon_cache::cached_icon;
+use super::long_path::long_path;
+use super::pack_type::{attention_message_for, attention_message_with, AttentionReason, PackInfo, PackType};
+use super::skin_pack_4d::skins_json_textures;
 use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::{Read, Write};
 use std::path::Path;
 use zip::ZipArchive;
 
-pub fn scan_single_pack(file_path: &Path) -> Vec<PackInfo> {
+pub fn scan_single_pack(file_path: &Path, icon_names: &[String]) -> Vec<PackInfo> {
+    let mut packs = scan_archive(file_path, icon_names);
+    if !packs.is_empty() && archive_has_mac_junk(file_path) {
+        let reason = AttentionReason::MacJunkFiles;
+        for pack in &mut packs {
+            pack.attention_reasons.push(reason);
+            pack.needs_attention = Some(true);
+            // Appended so detail already in the message (e.g. missing textures) survives
+            pack.attention_message = Some(match pack.attention_message.take() {
+                Some(message) => format!("{} {}.", message, reason.message()),
+                None => format!("{}.", reason.message()),
+            });
+        }
+    }
+    packs
+}
+
+/// Finder metadata that macOS adds when zipping: `__MACOSX/` trees and
+/// `.DS_Store` files. Bedrock on Windows can fail to load packs containing them.
+fn is_mac_junk(entry_name: &str) -> bool {
+    entry_name
+        .split(['/', '\\'])
+        .any(|part| part == "__MACOSX" || part == ".DS_Store")
+}
+
+fn archive_has_mac_junk(file_path: &Path) -> bool {
+    fs::File::open(file_path)
+        .ok()
+        .and_then(|file| ZipArchive::new(file).ok())
+        .is_some_and(|archive| archive.file_names().any(is_mac_junk))
+}
+
+fn scan_archive(file_path: &Path, icon_names: &[String]) -> Vec<PackInfo> {
     let file = match fs::File::open(file_path) {
         Ok(f) => f,
         Err(_) => return vec![],
@@ -26,25 +63,42 @@ pub fn scan_single_pack(file_path: &Path) -> Vec<PackInfo> {
     let is_mashup = is_mashup_name(&filename);
     let cleaned_name = clean_pack_name(&filename);
 
-    // Check for skins.json anywhere in the archive (not just root)
-    let mut has_skins_json = archive.by_name("skins.json").is_ok();
-    let mut skins_json_subfolder: Option<String> = None;
-
-    if !has_skins_json {
-        for i in 0..archive.len() {
-            if let Ok(file) = archive.by_index(i) {
-                let name = file.name();
-                if name.ends_with("skins.json") {
-                    has_skins_json = true;
-                    if let Some(idx) = name.rfind('/') {
-                        skins_json_subfolder = Some(name[..idx].to_string());
-                    }
-                    break;
-                }
-            }
-        }
+    // Entries of a password-protected zip can't be read, so surface the file
+    // with an explanation rather than letting it drop out of the results
+    if is_archive_encrypted(&mut archive) {
+        let attention_reasons = vec![AttentionReason::Encrypted];
+        return vec![PackInfo {
+            path: file_path.to_string_lossy().to_string(),
+            name: cleaned_name.clone(),
+            file_name: Some(cleaned_name),
+            pack_type: PackType::Unknown,
+            uuid: None,
+            version: None,
+            extracted: false,
+            icon_base64: None,
+            subfolder: None,
+            folder_size: None,
+            folder_size_formatted: None,
+            uncompressed_size: Some(archive_uncompressed_size(&mut archive, "")),
+            needs_attention: Some(true),
+            attention_message: attention_message_for(&attention_reasons),
+            attention_reasons,
+            is_installed: None,
+            is_update: None,
+            installed_version: None,
+            dependencies: Vec::new(),
+            description: None,
+            authors: Vec::new(),
+            format_version: None,
+            category: None,
+            linked_to: None,
+            receipt: None,
+            tags: Vec::new(),
+        }];
     }
 
+    let (has_skins_json, skins_json_subfolder) = find_skins_json_entry(&mut archive);
+
     if has_skins_json {
         let is_4d = check_4d_in_archive(&mut archive);
         let pack_type = if is_4d {
@@ -53,17 +107,35 @@ pub fn scan_single_pack(file_path: &Path) -> Vec<PackInfo> {
             PackType::SkinPack
         };
 
-        let (needs_attention, attention_message) = if is_4d {
+        let mut attention_reasons = if is_4d {
             check_4d_special_files(&mut archive)
         } else {
-            (false, None)
+            Vec::new()
         };
 
-        let icon = extract_icon_from_archive(&mut archive, "");
+        let icon = cached_archive_icon(&mut archive, file_path, "", icon_names);
+        let manifest_folder = skins_json_subfolder.clone().unwrap_or_default();
+        let missing_textures = find_missing_skin_textures(&mut archive, &manifest_folder);
+        if !missing_textures.is_empty() {
+            attention_reasons.push(AttentionReason::MissingTextures);
+        }
+        let attention_message = attention_message_with(&attention_reasons, |reason| match reason {
+            AttentionReason::MissingTextures => Some(format!(
+                "Skin pack references {} missing textures: {}",
+                missing_textures.len(),
+                missing_textures.join(", ")
+            )),
+            _ => None,
+        });
+        let manifest = read_archive_manifest(&mut archive, &manifest_folder);
+        let name = read_manifest_display_name(&mut archive, &manifest_folder, manifest.as_ref())
+            .unwrap_or_else(|| cleaned_name.clone());
+        let about = manifest_about(manifest.as_ref());
 
         return vec![PackInfo {
             path: file_path.to_string_lossy().to_string(),
-            name: cleaned_name,
+            name,
+            file_name: Some(cleaned_name),
             pack_type,
             uuid: None,
             version: None,
@@ -72,24 +144,39 @@ pub fn scan_single_pack(file_path: &Path) -> Vec<PackInfo> {
             subfolder: skins_json_subfolder,
             folder_size: None,
             folder_size_formatted: None,
-            needs_attention: Some(needs_attention),
+            uncompressed_size: Some(archive_uncompressed_size(&mut archive, "")),
+            needs_attention: Some(!attention_reasons.is_empty()),
             attention_message,
+            attention_reasons,
             is_installed: None,
             is_update: None,
             installed_version: None,
+            dependencies: about.dependencies,
+            description: about.description,
+            authors: about.authors,
+            format_version: about.format_version,
+            category: None,
+            linked_to: None,
+            receipt: None,
+            tags: Vec::new(),
         }];
     }
 
     let subfolders = detect_subfolders(&mut archive);
 
     if !subfolders.is_empty() {
-        return process_multi_pack_archive(file_path, &mut archive, &subfolders);
+        return process_multi_pack_archive(file_path, &mut archive, &subfolders, icon_names);
     }
 
-    let (pack_type, uuid, version) = get_pack_info_from_archive(&mut archive);
-    let icon = extract_icon_from_archive(&mut archive, "");
+    let manifest = read_archive_manifest(&mut archive, "");
+    let (pack_type, uuid, version) = get_pack_info_from_manifest(manifest.as_ref(), "");
+    let (pack_type, attention_reasons) = explain_manifestless(&mut archive, pack_type, uuid.is_none() && version.is_none());
+    let icon = cached_archive_icon(&mut archive, file_path, "", icon_names);
+    let name = read_manifest_display_name(&mut archive, "", manifest.as_ref()).unwrap_or_else(|| cleaned_name.clone());
+    let about = manifest_about(manifest.as_ref());
 
-    // Override to MashupPack if name indicates mashup and it's a world template
+    // Override to MashupPack if name indicates mashup and it's a world template.
+    // Manifests declaring skin/resource modules are already classified as MashupPack.
     let final_type = if is_mashup && pack_type == PackType::WorldTemplate {
         PackType::MashupPack
     } else {
@@ -98,7 +185,8 @@ pub fn scan_single_pack(file_path: &Path) -> Vec<PackInfo> {
 
     vec![PackInfo {
         path: file_path.to_string_lossy().to_string(),
-        name: cleaned_name,
+        name,
+        file_name: Some(cleaned_name),
         pack_type: final_type,
         uuid,
         version,
@@ -107,22 +195,262 @@ pub fn scan_single_pack(file_path: &Path) -> Vec<PackInfo> {
         subfolder: None,
         folder_size: None,
         folder_size_formatted: None,
-        needs_attention: None,
-        attention_message: None,
+        uncompressed_size: Some(archive_uncompressed_size(&mut archive, "")),
+        needs_attention: (!attention_reasons.is_empty()).then_some(true),
+        attention_message: attention_message_for(&attention_reasons),
+        attention_reasons,
         is_installed: None,
         is_update: None,
         installed_version: None,
+        dependencies: about.dependencies,
+        description: about.description,
+        authors: about.authors,
+        format_version: about.format_version,
+        category: None,
+        linked_to: None,
+        receipt: None,
+        tags: Vec::new(),
     }]
 }
 
+/// Top-level folders that only show up in one kind of pack.
+const BEHAVIOR_PACK_FOLDERS: &[&str] = &["scripts", "entities"];
+const RESOURCE_PACK_FOLDERS: &[&str] = &["textures"];
+
+/// Best guess at the type of a pack with no manifest, from its top-level folders.
+fn guess_type_from_folders(archive: &mut ZipArchive<fs::File>) -> Option<PackType> {
+    let top_level: std::collections::HashSet<String> = archive
+        .file_names()
+        .filter_map(|name| name.trim_start_matches('/').split_once('/').map(|(dir, _)| dir.to_lowercase()))
+        .collect();
+    if BEHAVIOR_PACK_FOLDERS.iter().any(|f| top_level.contains(*f)) {
+        Some(PackType::BehaviorPack)
+    } else if RESOURCE_PACK_FOLDERS.iter().any(|f| top_level.contains(*f)) {
+        Some(PackType::ResourcePack)
+    } else {
+        None
+    }
+}
+
+/// For a single-pack archive with no readable manifest, flags it as such and
+/// falls back to a low-confidence guess from folder names.
+fn explain_manifestless(
+    archive: &mut ZipArchive<fs::File>,
+    pack_type: PackType,
+    no_manifest_data: bool,
+) -> (PackType, Vec<AttentionReason>) {
+    if pack_type != PackType::Unknown || !no_manifest_data {
+        return (pack_type, Vec::new());
+    }
+    let mut reasons = vec![AttentionReason::MissingManifest];
+    match guess_type_from_folders(archive) {
+        Some(guess) => {
+            reasons.push(AttentionReason::GuessedType);
+            (guess, reasons)
+        }
+        None => (pack_type, reasons),
+    }
+}
+
+/// Checks for skins.json anywhere in the archive (not just root). Returns
+/// whether one was found and the folder it sits in, if not the root.
+fn find_skins_json_entry(archive: &mut ZipArchive<fs::File>) -> (bool, Option<String>) {
+    if archive.by_name("skins.json").is_ok() {
+        return (true, None);
+    }
+    for i in 0..archive.len() {
+        if let Ok(file) = archive.by_index(i) {
+            let name = file.name();
+            if name.ends_with("skins.json") && !is_mac_junk(name) {
+                return (true, name.rfind('/').map(|idx| name[..idx].to_string()));
+            }
+        }
+    }
+    (false, None)
+}
+
+/// The pack type(s) scan_single_pack would report for `file_path`, one per
+/// pack in a multi-pack archive, without reading icons or other metadata.
+pub fn classify_archive(file_path: &Path) -> Result<Vec<PackType>, String> {
+    let file = fs::File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    if is_archive_encrypted(&mut archive) {
+        return Ok(vec![PackType::Unknown]);
+    }
+
+    if find_skins_json_entry(&mut archive).0 {
+        return Ok(vec![if check_4d_in_archive(&mut archive) {
+            PackType::SkinPack4D
+        } else {
+            PackType::SkinPack
+        }]);
+    }
+
+    let is_mashup = file_path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .is_some_and(is_mashup_name);
+
+    let subfolders = detect_subfolders(&mut archive);
+    let types: Vec<PackType> = subfolders
+        .iter()
+        .map(|subfolder| {
+            if is_mashup {
+                PackType::MashupPack
+            } else {
+                let manifest = read_archive_manifest(&mut archive, subfolder);
+                get_pack_info_from_manifest(manifest.as_ref(), subfolder).0
+            }
+        })
+        .collect();
+    if !types.is_empty() {
+        return Ok(types);
+    }
+
+    let manifest = read_archive_manifest(&mut archive, "");
+    let (pack_type, uuid, version) = get_pack_info_from_manifest(manifest.as_ref(), "");
+    let (pack_type, _) = explain_manifestless(&mut archive, pack_type, uuid.is_none() && version.is_none());
+    Ok(vec![if is_mashup && pack_type == PackType::WorldTemplate {
+        PackType::MashupPack
+    } else {
+        pack_type
+    }])
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveProblem {
+    pub entry: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveHealth {
+    pub path: String,
+    pub total_entries: usize,
+    pub good_entries: usize,
+    pub problems: Vec<ArchiveProblem>,
+    pub is_healthy: bool,
+}
+
+/// Reads every entry to the end; the zip crate checks each CRC as the last
+/// bytes are read, so truncated or corrupt entries surface as read errors.
+pub fn verify_archive_entries(file_path: &Path) -> Result<ArchiveHealth, String> {
+    let file = fs::File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut archive = ZipArchive::new(std::io::BufReader::new(file))
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    let total_entries = archive.len();
+    let mut good_entries = 0;
+    let mut problems = Vec::new();
+    let mut buffer = vec![0u8; EXTRACT_BUFFER_SIZE];
+
+    for i in 0..total_entries {
+        let name = archive
+            .name_for_index(i)
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| format!("#{}", i));
+        let mut entry = match archive.by_index(i) {
+            Ok(entry) => entry,
+            Err(e) => {
+                problems.push(ArchiveProblem { entry: name, error: e.to_string() });
+                continue;
+            }
+        };
+
+        let result = loop {
+            match entry.read(&mut buffer) {
+                Ok(0) => break Ok(()),
+                Ok(_) => continue,
+                Err(e) => break Err(e),
+            }
+        };
+        match result {
+            Ok(()) => good_entries += 1,
+            Err(e) => problems.push(ArchiveProblem { entry: name, error: e.to_string() }),
+        }
+    }
+
+    Ok(ArchiveHealth {
+        path: file_path.to_string_lossy().to_string(),
+        total_entries,
+        good_entries,
+        is_healthy: problems.is_empty(),
+        problems,
+    })
+}
+
+/// Reads the header version from the manifest of a pack archive (or one of its
+/// subfolders), for callers that only have the file path.
+pub fn read_archive_manifest_version(file_path: &Path, subfolder: Option<&str>) -> Option<String> {
+    let file = fs::File::open(file_path).ok()?;
+    let mut archive = ZipArchive::new(file).ok()?;
+    let manifest_path = archive_path(subfolder.unwrap_or(""), "manifest.json");
+    let content = read_archive_text(&mut archive, &manifest_path)?;
+    let json = parse_manifest_json(&content)?;
+    extract_version(&json)
+}
+
+/// Installed size of the archive, or of one pack subfolder in it, read from
+/// the central directory without decompressing anything.
+fn archive_uncompressed_size(archive: &mut ZipArchive<fs::File>, subfolder: &str) -> u64 {
+    let prefix = if subfolder.is_empty() { String::new() } else { format!("{}/", subfolder) };
+    (0..archive.len())
+        .filter_map(|i| archive.by_index_raw(i).ok().map(|entry| (entry.name().replace('\\', "/"), entry.size())))
+        .filter(|(name, _)| name.starts_with(&prefix))
+        .map(|(_, size)| size)
+        .sum()
+}
+
+fn is_archive_encrypted(archive: &mut ZipArchive<fs::File>) -> bool {
+    (0..archive.len()).any(|i| {
+        archive
+            .by_index_raw(i)
+            .map(|f| f.encrypted())
+            .unwrap_or(false)
+    })
+}
+
+pub fn has_zip_magic(file_path: &Path) -> bool {
+    let mut file = match fs::File::open(file_path) {
+        Ok(f) => f,
+        Err(_) => return false,
+    };
+
+    let mut magic = [0u8; 4];
+    file.read_exact(&mut magic).is_ok() && magic == *b"PK\x03\x04"
+}
+
+pub fn compute_file_hash(file_path: &Path) -> Result<String, String> {
+    let file = fs::File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut reader = std::io::BufReader::new(file);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 256 * 1024];
+
+    loop {
+        let bytes_read = reader
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read: {}", e))?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
 fn is_mashup_name(name: &str) -> bool {
     let lower = name.to_lowercase();
     lower.contains("mashup") || lower.contains("mash-up") || lower.contains("mash up")
 }
 
-fn check_4d_special_files(archive: &mut ZipArchive<fs::File>) -> (bool, Option<String>) {
+fn check_4d_special_files(archive: &mut ZipArchive<fs::File>) -> Vec<AttentionReason> {
     let mut has_readme = false;
-    let mut has_multiple_geometry_folders = false;
     let mut geometry_folders = std::collections::HashSet::new();
 
     for i in 0..archive.len() {
@@ -146,25 +474,32 @@ fn check_4d_special_files(archive: &mut ZipArchive<fs::File>) -> (bool, Option<S
         }
     }
 
+    let mut reasons = Vec::new();
+    if has_readme {
+        reasons.push(AttentionReason::ContainsReadme);
+    }
     if geometry_folders.len() > 1 {
-        has_multiple_geometry_folders = true;
+        reasons.push(AttentionReason::MultipleGeometryFolders);
     }
+    reasons
+}
 
-    if has_readme || has_multiple_geometry_folders {
-        let mut messages = Vec::new();
-        if has_readme {
-            messages.push("Contains instructions/readme");
-        }
-        if has_multiple_geometry_folders {
-            messages.push("Multiple geometry folders detected");
-        }
-        messages.push("May require manual setup");
-        messages.push("SkinMaster may not work with this pack");
-
-        (true, Some(messages.join(". ") + "."))
-    } else {
-        (false, None)
-    }
+/// Texture and cape files named in skins.json that the archive doesn't contain.
+/// These show up as invisible skins in game.
+fn find_missing_skin_textures(archive: &mut ZipArchive<fs::File>, subfolder: &str) -> Vec<String> {
+    let skins_path = archive_path(subfolder, "skins.json");
+    let Some(json) = read_archive_text(archive, &skins_path).and_then(|c| parse_manifest_json(&c)) else {
+        return Vec::new();
+    };
+    let present: std::collections::HashSet<String> = archive
+        .file_names()
+        .map(|n| n.replace('\\', "/").to_lowercase())
+        .collect();
+
+    skins_json_textures(&json)
+        .into_iter()
+        .filter(|texture| !present.contains(&archive_path(subfolder, texture).to_lowercase()))
+        .collect()
 }
 
 fn detect_subfolders(archive: &mut ZipArchive<fs::File>) -> Vec<String> {
@@ -179,7 +514,7 @@ fn detect_subfolders(archive: &mut ZipArchive<fs::File>) -> Vec<String> {
         if let Ok(mut file) = archive.by_index(i) {
             let name = file.name().to_string();
 
-            if name.ends_with("manifest.json") {
+            if name.ends_with("manifest.json") && !is_mac_junk(&name) {
                 // Get the folder containing manifest.json
                 if let Some(idx) = name.rfind('/') {
                     let folder = &name[..idx];
@@ -205,7 +540,7 @@ fn detect_subfolders(archive: &mut ZipArchive<fs::File>) -> Vec<String> {
         // Check if this is a root manifest
         let is_root = !path.contains('/');
         if is_root {
-            if let Ok(json) = serde_json::from_str::<Value>(content) {
+            if let Some(json) = parse_manifest_json(content) {
                 if let Some(modules) = json.get("modules").and_then(|m| m.as_array()) {
                     for module in modules {
                         if let Some(type_str) = module.get("type").and_then(|t| t.as_str()) {
@@ -291,40 +626,46 @@ fn detect_subfolders(archive: &mut ZipArchive<fs::File>) -> Vec<String> {
         return vec![];
     }
 
-    // Sort: behavior packs first (ppack0, behavior_packs/*), then resource packs (ppack1, resource_packs/*)
-    subfolders.sort_by(|a, b| {
-        let a_lower = a.to_lowercase();
-        let b_lower = b.to_lowercase();
-
-        fn is_behavior_pack(s: &str) -> bool {
-            s.contains("behavior")
-                || s.contains("behaviour")
-                || s.contains("ppack0")
-                || s.contains("/bp0")
-                || s.contains("/bp1")
-                || s.ends_with("pack0")
-                || (s.contains("ppack") && s.contains("0"))
-        }
-
-        let a_is_bp = is_behavior_pack(&a_lower);
-        let b_is_bp = is_behavior_pack(&b_lower);
-
-        if a_is_bp && !b_is_bp {
-            std::cmp::Ordering::Less
-        } else if !a_is_bp && b_is_bp {
-            std::cmp::Ordering::Greater
-        } else {
-            a_lower.cmp(&b_lower)
-        }
+    // Sort: behavior packs first, then resource packs, going by the type each
+    // manifest declares so generically named folders ("1", "2") order right.
+    // Folder names only decide when the manifest doesn't say.
+    let manifest_types: std::collections::HashMap<&str, PackType> = manifest_contents
+        .iter()
+        .filter_map(|(path, content)| {
+            let (folder, _) = path.rsplit_once('/')?;
+            Some((folder, determine_pack_type(&parse_manifest_json(content)?)))
+        })
+        .collect();
+    subfolders.sort_by_cached_key(|folder| {
+        let lower = folder.to_lowercase();
+        let rank = match manifest_types.get(folder.as_str()) {
+            Some(pack_type) if *pack_type != PackType::Unknown => pack_type.install_rank(),
+            _ if looks_like_behavior_pack(&lower) => PackType::BehaviorPack.install_rank(),
+            _ => PackType::ResourcePack.install_rank(),
+        };
+        (rank, lower)
     });
 
     subfolders
 }
 
+/// Name heuristic for a behavior pack folder (ppack0, behavior_packs/*), used
+/// when its manifest doesn't declare a type.
+fn looks_like_behavior_pack(folder_lower: &str) -> bool {
+    folder_lower.contains("behavior")
+        || folder_lower.contains("behaviour")
+        || folder_lower.contains("ppack0")
+        || folder_lower.contains("/bp0")
+        || folder_lower.contains("/bp1")
+        || folder_lower.ends_with("pack0")
+        || (folder_lower.contains("ppack") && folder_lower.contains("0"))
+}
+
 fn process_multi_pack_archive(
     file_path: &Path,
     archive: &mut ZipArchive<fs::File>,
     subfolders: &[String],
+    icon_names: &[String],
 ) -> Vec<PackInfo> {
     let mut packs = Vec::new();
     let base_filename = file_path
@@ -337,8 +678,12 @@ fn process_multi_pack_archive(
     let is_mashup = is_mashup_name(&base_filename);
 
     for subfolder in subfolders.iter() {
-        let (mut pack_type, uuid, version) = get_pack_info_from_subfolder(archive, subfolder);
-        let icon = extract_icon_from_archive(archive, subfolder);
+        let manifest = read_archive_manifest(archive, subfolder);
+        let (mut pack_type, uuid, version) = get_pack_info_from_manifest(manifest.as_ref(), subfolder);
+        let icon = cached_archive_icon(archive, file_path, subfolder, icon_names);
+        let name = read_manifest_display_name(archive, subfolder, manifest.as_ref())
+            .unwrap_or_else(|| cleaned_name.clone());
+        let about = manifest_about(manifest.as_ref());
 
         // Override to MashupPack if filename indicates mash-up
         if is_mashup {
@@ -347,7 +692,8 @@ fn process_multi_pack_archive(
 
         packs.push(PackInfo {
             path: file_path.to_string_lossy().to_string(),
-            name: cleaned_name.clone(),
+            name,
+            file_name: Some(cleaned_name.clone()),
             pack_type,
             uuid,
             version,
@@ -356,21 +702,35 @@ fn process_multi_pack_archive(
             subfolder: Some(subfolder.clone()),
             folder_size: None,
             folder_size_formatted: None,
+            uncompressed_size: Some(archive_uncompressed_size(archive, subfolder)),
             needs_attention: None,
             attention_message: None,
+            attention_reasons: Vec::new(),
             is_installed: None,
             is_update: None,
             installed_version: None,
+            dependencies: about.dependencies,
+            description: about.description,
+            authors: about.authors,
+            format_version: about.format_version,
+            category: None,
+            linked_to: None,
+            receipt: None,
+            tags: Vec::new(),
         });
     }
 
     if packs.is_empty() {
-        let (pack_type, uuid, version) = get_pack_info_from_archive(archive);
-        let icon = extract_icon_from_archive(archive, "");
+        let manifest = read_archive_manifest(archive, "");
+        let (pack_type, uuid, version) = get_pack_info_from_manifest(manifest.as_ref(), "");
+        let icon = cached_archive_icon(archive, file_path, "", icon_names);
+        let name = read_manifest_display_name(archive, "", manifest.as_ref()).unwrap_or_else(|| cleaned_name.clone());
+        let about = manifest_about(manifest.as_ref());
 
         packs.push(PackInfo {
             path: file_path.to_string_lossy().to_string(),
-            name: cleaned_name,
+            name,
+            file_name: Some(cleaned_name),
             pack_type,
             uuid,
             version,
@@ -379,11 +739,21 @@ fn process_multi_pack_archive(
             subfolder: None,
             folder_size: None,
             folder_size_formatted: None,
+            uncompressed_size: Some(archive_uncompressed_size(archive, "")),
             needs_attention: None,
             attention_message: None,
+            attention_reasons: Vec::new(),
             is_installed: None,
             is_update: None,
             installed_version: None,
+            dependencies: about.dependencies,
+            description: about.description,
+            authors: about.authors,
+            format_version: about.format_version,
+            category: None,
+            linked_to: None,
+            receipt: None,
+            tags: Vec::new(),
         });
     }
 
@@ -450,47 +820,340 @@ fn clean_pack_name(name: &str) -> String {
     cleaned.trim().to_string()
 }
 
-fn get_pack_info_from_subfolder(
+fn archive_path(subfolder: &str, file: &str) -> String {
+    if subfolder.is_empty() {
+        file.to_string()
+    } else {
+        format!("{}/{}", subfolder, file)
+    }
+}
+
+fn read_archive_text(archive: &mut ZipArchive<fs::File>, path: &str) -> Option<String> {
+    let mut file = archive.by_name(path).ok()?;
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+/// Parsed manifest.json of `subfolder`, read once per pack and handed to the
+/// helpers below.
+fn read_archive_manifest(archive: &mut ZipArchive<fs::File>, subfolder: &str) -> Option<Value> {
+    let content = read_archive_text(archive, &archive_path(subfolder, "manifest.json"))?;
+    parse_manifest_json(&content)
+}
+
+// Prefers the manifest header name over the filename, resolving localization
+// keys such as "pack.name" through texts/en_US.lang.
+fn read_manifest_display_name(
     archive: &mut ZipArchive<fs::File>,
     subfolder: &str,
-) -> (PackType, Option<String>, Option<String>) {
-    let manifest_path = format!("{}/manifest.json", subfolder);
-
-    if let Ok(mut file) = archive.by_name(&manifest_path) {
-        let mut content = String::new();
-        if file.read_to_string(&mut content).is_ok() {
-            if let Ok(json) = serde_json::from_str::<Value>(&content) {
-                let pack_type = determine_pack_type(&json);
-                let uuid = extract_uuid(&json);
-                let version = extract_version(&json);
-
-                if pack_type == PackType::Unknown {
-                    let subfolder_lower = subfolder.to_lowercase();
-                    let fallback_type = if subfolder_lower.contains("behavior")
-                        || subfolder_lower.contains("behaviour")
-                        || subfolder_lower == "ppack0"
-                        || subfolder_lower.ends_with("/ppack0")
-                    {
-                        PackType::BehaviorPack
-                    } else if subfolder_lower.contains("resource")
-                        || subfolder_lower == "ppack1"
-                        || subfolder_lower.ends_with("/ppack1")
-                    {
-                        PackType::ResourcePack
-                    } else {
-                        pack_type
-                    };
-                    return (fallback_type, uuid, version);
-                }
+    manifest: Option<&Value>,
+) -> Option<String> {
+    let json = manifest?;
+    let lang = read_archive_text(archive, &archive_path(subfolder, "texts/en_US.lang"));
+    resolve_display_name(json, lang.as_deref())
+}
 
-                return (pack_type, uuid, version);
+/// Folder counterpart of read_manifest_display_name for extracted packs.
+pub fn read_folder_display_name(folder: &Path) -> Option<String> {
+    let manifest_path = folder.join("manifest.json");
+    let content = fs::read_to_string(&manifest_path).ok()?;
+    let json = parse_manifest_json(&content)?;
+    let lang = fs::read_to_string(folder.join("texts").join("en_US.lang")).ok();
+    resolve_display_name(&json, lang.as_deref())
+}
+
+fn resolve_display_name(json: &Value, lang: Option<&str>) -> Option<String> {
+    let raw_name = json
+        .get("header")
+        .and_then(|h| h.get("name"))
+        .and_then(|n| n.as_str())?
+        .trim()
+        .to_string();
+
+    if raw_name.is_empty() {
+        return None;
+    }
+
+    if let Some(lang) = lang {
+        let keys = [raw_name.clone(), format!("skinpack.{}", raw_name)];
+        for line in lang.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                if keys.iter().any(|k| k == key.trim()) {
+                    let value = value.split('#').next().unwrap_or(value);
+                    let cleaned = sanitize_display_name(value);
+                    if !cleaned.is_empty() {
+                        return Some(cleaned);
+                    }
+                }
             }
         }
     }
 
-    let subfolder_lower = subfolder.to_lowercase();
+    let is_localization_key = !raw_name.contains(' ')
+        && raw_name.contains('.')
+        && raw_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_');
+    if is_localization_key {
+        return None;
+    }
+
+    let cleaned = sanitize_display_name(&raw_name);
+    if cleaned.is_empty() {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonError {
+    pub file: String,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    /// Parses once comments and trailing commas are allowed, which the game
+    /// accepts in most files
+    pub lenient_ok: bool,
+}
+
+/// Strict parse of one pack JSON file; None when it's valid.
+pub fn json_error(file: &str, bytes: &[u8]) -> Option<JsonError> {
+    let content = match std::str::from_utf8(bytes) {
+        Ok(content) => content.trim_start_matches('\u{feff}'),
+        Err(e) => {
+            return Some(JsonError {
+                file: file.to_string(),
+                message: format!("Not valid UTF-8: {}", e),
+                line: 0,
+                column: 0,
+                lenient_ok: false,
+            })
+        }
+    };
+    let err = serde_json::from_str::<Value>(content).err()?;
+    Some(JsonError {
+        file: file.to_string(),
+        message: err.to_string(),
+        line: err.line(),
+        column: err.column(),
+        lenient_ok: json5::from_str::<Value>(content).is_ok(),
+    })
+}
+
+/// Relative paths and contents of every .json entry in a pack archive.
+pub fn read_archive_json_files(path: &Path) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Failed to read archive: {}", e))?;
+    let mut files = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        let name = entry.name().replace('\\', "/");
+        if entry.is_dir() || is_mac_junk(&name) || !name.to_lowercase().ends_with(".json") {
+            continue;
+        }
+        // No pre-sizing from the header: a forged entry size would allocate up front.
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        files.push((name, bytes));
+    }
+    Ok(files)
+}
+
+/// Parses a manifest, tolerating a UTF-8 BOM and, when strict parsing fails,
+/// the comments and trailing commas of hand-edited JSON5.
+pub fn parse_manifest_json(content: &str) -> Option<Value> {
+    parse_manifest_json_lenient(content).map(|(json, _)| json)
+}
+
+/// parse_manifest_json that also says whether the JSON5 fallback was needed,
+/// for callers that log it.
+pub fn parse_manifest_json_lenient(content: &str) -> Option<(Value, bool)> {
+    let content = content.trim_start_matches('\u{feff}');
+    match serde_json::from_str::<Value>(content) {
+        Ok(json) => Some((json, false)),
+        Err(_) => json5::from_str::<Value>(content).ok().map(|json| (json, true)),
+    }
+}
+
+#[derive(Default)]
+struct ManifestAbout {
+    // Only pack dependencies declared by UUID; script module dependencies are skipped.
+    dependencies: Vec<String>,
+    description: Option<String>,
+    authors: Vec<String>,
+    format_version: Option<u32>,
+}
+
+fn manifest_about(manifest: Option<&Value>) -> ManifestAbout {
+    manifest
+        .map(|json| ManifestAbout {
+            dependencies: extract_dependencies(json),
+            description: extract_description(json),
+            authors: extract_authors(json),
+            format_version: extract_format_version(json),
+        })
+        .unwrap_or_default()
+}
+
+/// Top-level format_version, written either as a number or a string like "2".
+pub fn extract_format_version(json: &Value) -> Option<u32> {
+    match json.get("format_version")? {
+        Value::Number(n) => n.as_u64().map(|v| v as u32),
+        Value::String(s) => s.trim().split('.').next()?.parse().ok(),
+        _ => None,
+    }
+}
+
+/// header.description with § formatting codes removed.
+pub fn extract_description(json: &Value) -> Option<String> {
+    let raw = json.get("header")?.get("description")?.as_str()?;
+    let result = strip_formatting_codes(raw).trim().to_string();
+    if result.is_empty() { None } else { Some(result) }
+}
+
+/// metadata.authors, which Minecraft accepts as either a string or an array of strings.
+pub fn extract_authors(json: &Value) -> Vec<String> {
+    let authors = match json.get("metadata").and_then(|m| m.get("authors")) {
+        Some(Value::String(author)) => vec![author.clone()],
+        Some(Value::Array(list)) => list
+            .iter()
+            .filter_map(|a| a.as_str())
+            .map(|a| a.to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+    authors
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+pub fn extract_dependencies(json: &Value) -> Vec<String> {
+    json.get("dependencies")
+        .and_then(|d| d.as_array())
+        .map(|deps| {
+            deps.iter()
+                .filter_map(|dep| dep.get("uuid").and_then(|u| u.as_str()))
+                .map(|u| u.to_lowercase())
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
-    let pack_type = if subfolder_lower.contains("behavior")
+const RESERVED_WINDOWS_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Characters Windows rejects in file and folder names.
+fn is_windows_illegal_char(c: char) -> bool {
+    matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') || c.is_control()
+}
+
+fn is_illegal_folder_char(c: char) -> bool {
+    if cfg!(windows) {
+        is_windows_illegal_char(c)
+    } else {
+        c == '/' || c == '\0'
+    }
+}
+
+fn is_reserved_folder_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim();
+    cfg!(windows) && RESERVED_WINDOWS_NAMES.iter().any(|r| r.eq_ignore_ascii_case(stem))
+}
+
+/// Makes a pack name safe to use as a folder name on this platform: drops
+/// characters the filesystem rejects, trailing dots and spaces (Windows) and
+/// suffixes reserved device names such as CON.
+pub fn sanitize_folder_name(name: &str) -> String {
+    let cleaned: String = name.chars().filter(|c| !is_illegal_folder_char(*c)).collect();
+    let mut cleaned = if cfg!(windows) {
+        cleaned.trim().trim_end_matches(['.', ' ']).to_string()
+    } else {
+        cleaned.trim().to_string()
+    };
+
+    if cleaned.is_empty() || cleaned == "." || cleaned == ".." {
+        return "Unnamed Pack".to_string();
+    }
+    if is_reserved_folder_name(&cleaned) {
+        cleaned.push('_');
+    }
+    cleaned
+}
+
+/// Human-readable reasons sanitize_folder_name would change `name`.
+pub fn folder_name_issues(name: &str) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    let mut illegal: Vec<char> = Vec::new();
+    for c in name.chars().filter(|c| is_illegal_folder_char(*c)) {
+        if !illegal.contains(&c) {
+            illegal.push(c);
+        }
+    }
+    for c in illegal {
+        if c.is_control() {
+            issues.push(format!("Contains control character U+{:04X}", c as u32));
+        } else {
+            issues.push(format!("Contains '{}'", c));
+        }
+    }
+    if name.trim() != name {
+        issues.push("Leading or trailing spaces".to_string());
+    }
+    if cfg!(windows) && name.trim_end().ends_with('.') {
+        issues.push("Ends with a dot".to_string());
+    }
+    if is_reserved_folder_name(name) {
+        issues.push(format!("'{}' is a reserved name on Windows", name.trim()));
+    }
+    if name.trim().is_empty() {
+        issues.push("Name is empty".to_string());
+    }
+    issues
+}
+
+/// Removes Minecraft § formatting codes along with the character after each.
+fn strip_formatting_codes(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+            continue;
+        }
+        result.push(c);
+    }
+    result
+}
+
+// Strips Minecraft formatting codes and characters that are not valid in folder names.
+fn sanitize_display_name(name: &str) -> String {
+    let result: String = strip_formatting_codes(name)
+        .chars()
+        .filter(|&c| !is_windows_illegal_char(c))
+        .collect();
+    result.trim().trim_end_matches('.').trim().to_string()
+}
+
+fn get_pack_info_from_manifest(
+    manifest: Option<&Value>,
+    subfolder: &str,
+) -> (PackType, Option<String>, Option<String>) {
+    let (pack_type, uuid, version) = match manifest {
+        Some(json) => (determine_pack_type(json), extract_uuid(json), extract_version(json)),
+        None => (PackType::Unknown, None, None),
+    };
+    if pack_type != PackType::Unknown {
+        return (pack_type, uuid, version);
+    }
+
+    let subfolder_lower = subfolder.to_lowercase();
+    let fallback_type = if subfolder_lower.contains("behavior")
         || subfolder_lower.contains("behaviour")
         || subfolder_lower == "ppack0"
         || subfolder_lower.ends_with("/ppack0")
@@ -505,91 +1168,100 @@ fn get_pack_info_from_subfolder(
         PackType::Unknown
     };
 
-    (pack_type, None, None)
+    (fallback_type, uuid, version)
 }
 
-fn get_pack_info_from_archive(
+fn cached_archive_icon(
     archive: &mut ZipArchive<fs::File>,
-) -> (PackType, Option<String>, Option<String>) {
-    if let Ok(mut file) = archive.by_name("manifest.json") {
-        let mut content = String::new();
-        if file.read_to_string(&mut content).is_ok() {
-            if let Ok(json) = serde_json::from_str::<Value>(&content) {
-                let pack_type = determine_pack_type(&json);
-                let uuid = extract_uuid(&json);
-                let version = extract_version(&json);
-                return (pack_type, uuid, version);
-            }
-        }
-    }
-
-    (PackType::Unknown, None, None)
+    file_path: &Path,
+    subfolder: &str,
+    icon_names: &[String],
+) -> Option<String> {
+    let variant = format!("{}|{}", subfolder, icon_names.join(","));
+    cached_icon(file_path, &variant, || extract_icon_from_archive(archive, subfolder, icon_names))
 }
 
 fn extract_icon_from_archive(
     archive: &mut ZipArchive<fs::File>,
     subfolder: &str,
+    icon_names: &[String],
 ) -> Option<String> {
-    let icon_names = if subfolder.is_empty() {
-        vec![
-            "pack_icon.png".to_string(),
-            "Pack_Icon.png".to_string(),
-            "world_icon.jpeg".to_string(),
-            "world_icon.jpg".to_string(),
-        ]
-    } else {
-        vec![
-            format!("{}/pack_icon.png", subfolder),
-            format!("{}/Pack_Icon.png", subfolder),
-            format!("{}/world_icon.jpeg", subfolder),
-            format!("{}/world_icon.jpg", subfolder),
-        ]
-    };
-
-    for icon_name in &icon_names {
-        if let Ok(mut file) = archive.by_name(icon_name) {
+    for icon_name in icon_names {
+        if let Ok(mut file) = archive.by_name(&archive_path(subfolder, icon_name)) {
             let mut buffer = Vec::new();
             if file.read_to_end(&mut buffer).is_ok() {
-                let mime = if icon_name.ends_with(".jpg") || icon_name.ends_with(".jpeg") {
-                    "image/jpeg"
-                } else {
-                    "image/png"
-                };
-                return Some(format!("data:{};base64,{}", mime, general_purpose::STANDARD.encode(&buffer)));
+                if let Some(icon) = encode_icon_data(&buffer, icon_name) {
+                    return Some(icon);
+                }
             }
         }
     }
 
-    if subfolder.is_empty() {
-        let mut found_index: Option<usize> = None;
-        let mut found_is_jpeg = false;
-        for i in 0..archive.len() {
-            if let Ok(file) = archive.by_index(i) {
-                let name = file.name().to_lowercase();
-                if (name.ends_with("pack_icon.png")
-                    || name.ends_with("world_icon.jpeg")
-                    || name.ends_with("world_icon.jpg"))
-                    && !name.contains('/')
-                {
-                    found_is_jpeg = name.ends_with(".jpeg") || name.ends_with(".jpg");
-                    found_index = Some(i);
-                    break;
-                }
+    // Fallback: case-insensitive (e.g. PACK_ICON.PNG) and nested matches. The
+    // shallowest path wins, then name priority, then the path itself, so the
+    // choice doesn't depend on entry order and a subpack icon never beats the
+    // main one.
+    let prefix = archive_path(subfolder, "").to_lowercase();
+    let wanted: Vec<String> = icon_names.iter().map(|n| n.to_lowercase()).collect();
+    let mut best: Option<((usize, usize, String), usize)> = None;
+    for i in 0..archive.len() {
+        let Some(name) = archive.name_for_index(i) else {
+            continue;
+        };
+        let name = name.replace('\\', "/").to_lowercase();
+        let Some(relative) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+        let file_name = relative.rsplit('/').next().unwrap_or(relative);
+        if let Some(priority) = wanted.iter().position(|w| w == file_name) {
+            let key = (relative.matches('/').count(), priority, relative.to_string());
+            if best.as_ref().is_none_or(|(k, _)| key < *k) {
+                best = Some((key, i));
             }
         }
+    }
 
-        if let Some(idx) = found_index {
-            if let Ok(mut f) = archive.by_index(idx) {
-                let mut buffer = Vec::new();
-                if f.read_to_end(&mut buffer).is_ok() {
-                    let mime = if found_is_jpeg { "image/jpeg" } else { "image/png" };
-                    return Some(format!("data:{};base64,{}", mime, general_purpose::STANDARD.encode(&buffer)));
-                }
-            }
-        }
+    let ((_, priority, _), idx) = best?;
+    let mut file = archive.by_index(idx).ok()?;
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer).ok()?;
+    encode_icon_data(&buffer, &wanted[priority])
+}
+
+/// Turns icon bytes into a data URL. PNG and JPEG within the size limit pass
+/// through untouched; anything else (webp, tga, oversized images) is decoded
+/// and re-encoded as PNG. Returns None when the format can't be decoded.
+pub fn encode_icon_data(data: &[u8], file_name: &str) -> Option<String> {
+    const MAX_DIMENSION: u32 = 256;
+
+    // TGA has no magic bytes, so fall back to the file extension
+    let format = image::guess_format(data)
+        .ok()
+        .or_else(|| image::ImageFormat::from_path(file_name).ok())?;
+
+    let fits = image::ImageReader::with_format(std::io::Cursor::new(data), format)
+        .into_dimensions()
+        .map(|(w, h)| w <= MAX_DIMENSION && h <= MAX_DIMENSION)
+        .unwrap_or(true);
+
+    let passthrough_mime = match format {
+        image::ImageFormat::Png => Some("image/png"),
+        image::ImageFormat::Jpeg => Some("image/jpeg"),
+        _ => None,
+    };
+    if let (Some(mime), true) = (passthrough_mime, fits) {
+        return Some(format!("data:{};base64,{}", mime, general_purpose::STANDARD.encode(data)));
     }
 
-    None
+    let img = image::load_from_memory_with_format(data, format).ok()?;
+    let img = if fits {
+        img
+    } else {
+        img.resize(MAX_DIMENSION, MAX_DIMENSION, image::imageops::FilterType::Lanczos3)
+    };
+    let mut buf = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut buf), image::ImageFormat::Png).ok()?;
+    Some(format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(&buf)))
 }
 
 fn check_4d_in_archive(archive: &mut ZipArchive<fs::File>) -> bool {
@@ -604,14 +1276,14 @@ fn check_4d_in_archive(archive: &mut ZipArchive<fs::File>) -> bool {
     false
 }
 
-fn extract_uuid(json: &Value) -> Option<String> {
+pub fn extract_uuid(json: &Value) -> Option<String> {
     json.get("header")
         .and_then(|h| h.get("uuid"))
         .and_then(|u| u.as_str())
         .map(|s| s.to_string())
 }
 
-fn extract_version(json: &Value) -> Option<String> {
+pub fn extract_version(json: &Value) -> Option<String> {
     json.get("header")
         .and_then(|h| h.get("version"))
         .and_then(|v| {
@@ -632,6 +1304,12 @@ fn extract_version(json: &Value) -> Option<String> {
 }
 
 fn determine_pack_type(json: &Value) -> PackType {
+    // Marketplace mash-ups often ship a world template manifest that also
+    // declares skin or resource modules, without "mashup" in the name
+    if is_mashup_manifest(json) {
+        return PackType::MashupPack;
+    }
+
     // Check modules array
     if let Some(modules) = json.get("modules").and_then(|m| m.as_array()) {
         for module in modules {
@@ -676,6 +1354,22 @@ fn determine_pack_type(json: &Value) -> PackType {
     PackType::Unknown
 }
 
+fn is_mashup_manifest(json: &Value) -> bool {
+    let module_types: Vec<&str> = json
+        .get("modules")
+        .and_then(|m| m.as_array())
+        .map(|modules| {
+            modules
+                .iter()
+                .filter_map(|module| module.get("type").and_then(|t| t.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    module_types.contains(&"world_template")
+        && (module_types.contains(&"skin_pack") || module_types.contains(&"resources"))
+}
+
 pub fn extract_pack_to_destination(
     file_path: &Path,
     destination_dir: &Path,
@@ -683,36 +1377,77 @@ pub fn extract_pack_to_destination(
     subfolder: Option<&str>,
     output_name_override: Option<&str>,
 ) -> Result<String, String> {
+    extract_pack_excluding(file_path, destination_dir, pack_type, subfolder, output_name_override, &[])
+        .map(|(path, _)| path)
+}
+
+/// Entries left out of an extraction by Settings.extract_exclude_globs.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractExclusions {
+    pub files: usize,
+    pub bytes: u64,
+    /// Required files a pattern matched; these are extracted anyway
+    pub protected: Vec<String>,
+}
+
+const PROTECTED_PACK_FILES: &[&str] = &["manifest.json", "skins.json", "level.dat"];
+
+/// Patterns containing "/" match the whole relative path; others match any
+/// single path segment, so ".git" or "*.psd" work at any depth.
+fn exclude_matchers(globs: &[String]) -> Result<Vec<(regex::Regex, bool)>, String> {
+    globs
+        .iter()
+        .map(|g| g.trim())
+        .filter(|g| !g.is_empty())
+        .map(|g| Ok((glob_to_regex(g.trim_end_matches('/'))?, g.trim_matches('/').contains('/'))))
+        .collect()
+}
+
+fn is_excluded(matchers: &[(regex::Regex, bool)], relative_path: &str) -> bool {
+    let relative_path = relative_path.trim_end_matches('/');
+    matchers.iter().any(|(re, whole_path)| {
+        if *whole_path {
+            re.is_match(relative_path)
+        } else {
+            relative_path.split('/').any(|segment| re.is_match(segment))
+        }
+    })
+}
+
+/// extract_pack_to_destination that skips entries matching `exclude_globs`.
+pub fn extract_pack_excluding(
+    file_path: &Path,
+    destination_dir: &Path,
+    pack_type: PackType,
+    subfolder: Option<&str>,
+    output_name_override: Option<&str>,
+    exclude_globs: &[String],
+) -> Result<(String, ExtractExclusions), String> {
+    let matchers = exclude_matchers(exclude_globs)?;
+    let mut exclusions = ExtractExclusions::default();
+
     let filename = file_path
         .file_stem()
         .ok_or("Invalid filename")?
         .to_string_lossy()
         .to_string();
 
-    let type_suffix = match pack_type {
-        PackType::BehaviorPack => " (ADDON)",
-        PackType::ResourcePack => " (RESOURCE)",
-        PackType::SkinPack => " (SKIN)",
-        PackType::SkinPack4D => "",
-        PackType::WorldTemplate => " (TEMPLATE)",
-        PackType::MashupPack => " (MASHUP)",
-        PackType::Unknown => "",
-    };
+    let type_suffix = pack_type.folder_suffix();
 
     let output_name = if let Some(name) = output_name_override {
-        name.to_string()
+        sanitize_folder_name(name)
     } else {
-        format!("{}{}", filename, type_suffix)
+        sanitize_folder_name(&format!("{}{}", filename, type_suffix))
     };
 
     let output_path = destination_dir.join(&output_name);
 
     if output_path.exists() {
-        fs::remove_dir_all(&output_path)
+        fs::remove_dir_all(long_path(&output_path)?)
             .map_err(|e| format!("Failed to remove existing directory: {}", e))?;
     }
 
-    fs::create_dir_all(&output_path).map_err(|e| format!("Failed to create directory: {}", e))?;
+    fs::create_dir_all(long_path(&output_path)?).map_err(|e| format!("Failed to create directory: {}", e))?;
 
     let file = fs::File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
     let mut archive = ZipArchive::new(std::io::BufReader::new(file))
@@ -756,14 +1491,22 @@ pub fn extract_pack_to_destination(
             continue;
         }
 
-        if std::path::Path::new(relative_path)
-            .components()
-            .any(|c| c == std::path::Component::ParentDir)
-        {
-            return Err(format!(
-                "Security: Attempted path traversal in zip file: {}",
-                relative_path
-            ));
+        ensure_no_traversal(relative_path)?;
+
+        if is_mac_junk(relative_path) {
+            continue;
+        }
+
+        if !matchers.is_empty() && is_excluded(&matchers, relative_path) {
+            if PROTECTED_PACK_FILES.contains(&relative_path.to_lowercase().as_str()) {
+                exclusions.protected.push(relative_path.to_string());
+            } else {
+                if !name.ends_with('/') {
+                    exclusions.files += 1;
+                    exclusions.bytes += zip_file.size();
+                }
+                continue;
+            }
         }
 
         let outpath = output_path.join(relative_path);
@@ -788,32 +1531,338 @@ pub fn extract_pack_to_destination(
         .map_err(|e| format!("Failed to read archive: {}", e))?;
 
     for dir in dirs_to_create {
-        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+        fs::create_dir_all(long_path(&dir)?).map_err(|e| format!("Failed to create directory: {}", e))?;
     }
 
-    const BUFFER_SIZE: usize = 256 * 1024;
-    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let mut buffer = vec![0u8; EXTRACT_BUFFER_SIZE];
 
     for (i, outpath) in files_to_extract {
         let mut zip_file = archive
             .by_index(i)
             .map_err(|e| format!("Failed to read entry: {}", e))?;
-        let mut outfile =
-            fs::File::create(&outpath).map_err(|e| format!("Failed to create file: {}", e))?;
-        let mut writer = std::io::BufWriter::with_capacity(BUFFER_SIZE, &mut outfile);
-
-        loop {
-            let bytes_read = zip_file
-                .read(&mut buffer)
-                .map_err(|e| format!("Failed to read: {}", e))?;
-            if bytes_read == 0 {
-                break;
+        copy_entry_buffered(&mut zip_file, &outpath, &mut buffer)?;
+    }
+
+    Ok((output_path.to_string_lossy().to_string(), exclusions))
+}
+
+const EXTRACT_BUFFER_SIZE: usize = 256 * 1024;
+
+fn ensure_no_traversal(relative_path: &str) -> Result<(), String> {
+    if Path::new(relative_path)
+        .components()
+        .any(|c| c == std::path::Component::ParentDir)
+    {
+        return Err(format!(
+            "Security: Attempted path traversal in zip file: {}",
+            relative_path
+        ));
+    }
+    Ok(())
+}
+
+fn copy_entry_buffered(entry: &mut impl Read, outpath: &Path, buffer: &mut [u8]) -> Result<(), String> {
+    let mut outfile =
+        fs::File::create(long_path(outpath)?).map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut writer = std::io::BufWriter::with_capacity(EXTRACT_BUFFER_SIZE, &mut outfile);
+
+    loop {
+        let bytes_read = entry
+            .read(buffer)
+            .map_err(|e| format!("Failed to read: {}", e))?;
+        if bytes_read == 0 {
+            break;
+        }
+        writer
+            .write_all(&buffer[..bytes_read])
+            .map_err(|e| format!("Failed to write: {}", e))?;
+    }
+    writer.flush().map_err(|e| format!("Failed to write: {}", e))
+}
+
+/// Converts a glob such as "textures/**" or "*.json" into an anchored,
+/// case-insensitive regex over forward-slash archive paths.
+fn glob_to_regex(pattern: &str) -> Result<regex::Regex, String> {
+    let mut re = String::from("(?i)^");
+    let mut chars = pattern.trim_start_matches('/').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                re.push_str(".*");
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            other => re.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    re.push('$');
+    regex::Regex::new(&re).map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))
+}
+
+/// Extracts only the archive entries matching one of `patterns`, keeping their
+/// relative layout under `destination`. Returns the extracted relative paths.
+pub fn extract_matching_files(
+    file_path: &Path,
+    patterns: &[String],
+    destination: &Path,
+) -> Result<Vec<String>, String> {
+    let matchers = patterns
+        .iter()
+        .map(|p| glob_to_regex(p))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let file = fs::File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut archive = ZipArchive::new(std::io::BufReader::new(file))
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    let mut buffer = vec![0u8; EXTRACT_BUFFER_SIZE];
+    let mut extracted = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut zip_file = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read entry: {}", e))?;
+        if zip_file.is_dir() {
+            continue;
+        }
+
+        let name = zip_file.name().replace('\\', "/");
+        let relative_path = name.trim_start_matches('/').to_string();
+        if !matchers.iter().any(|m| m.is_match(&relative_path)) {
+            continue;
+        }
+        // enclosed_name also refuses absolute, drive and UNC entry names, which
+        // join() would otherwise let replace the destination entirely
+        let enclosed = zip_file.enclosed_name().ok_or_else(|| {
+            format!("Security: Attempted path traversal in zip file: {}", name)
+        })?;
+
+        let outpath = destination.join(enclosed);
+        if let Some(parent) = outpath.parent() {
+            fs::create_dir_all(long_path(parent)?).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        copy_entry_buffered(&mut zip_file, &outpath, &mut buffer)?;
+        extracted.push(relative_path);
+    }
+
+    Ok(extracted)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionInfo {
+    pub path: String,
+    pub total_entries: usize,
+    pub uncompressed_size: u64,
+    /// The compression fields are None for folders
+    pub compressed_size: Option<u64>,
+    /// compressed / uncompressed, so lower is better
+    pub ratio: Option<f64>,
+    /// Entries stored without compression, often already-compressed media
+    pub stored_entries: Option<usize>,
+}
+
+/// Compressed and uncompressed totals of a pack, read from the archive's
+/// central directory without decompressing anything.
+pub fn compression_info(path: &Path) -> Result<CompressionInfo, String> {
+    if path.is_dir() {
+        let files = list_pack_files(path)?;
+        return Ok(CompressionInfo {
+            path: path.to_string_lossy().to_string(),
+            total_entries: files.len(),
+            uncompressed_size: files.iter().map(|(_, size)| size).sum(),
+            compressed_size: None,
+            ratio: None,
+            stored_entries: None,
+        });
+    }
+
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Failed to read archive: {}", e))?;
+    let mut total_entries = 0;
+    let mut uncompressed_size = 0u64;
+    let mut compressed_size = 0u64;
+    let mut stored_entries = 0;
+    for i in 0..archive.len() {
+        let Ok(entry) = archive.by_index_raw(i) else { continue };
+        if entry.is_dir() {
+            continue;
+        }
+        total_entries += 1;
+        uncompressed_size += entry.size();
+        compressed_size += entry.compressed_size();
+        if entry.compression() == zip::CompressionMethod::Stored {
+            stored_entries += 1;
+        }
+    }
+
+    Ok(CompressionInfo {
+        path: path.to_string_lossy().to_string(),
+        total_entries,
+        uncompressed_size,
+        compressed_size: Some(compressed_size),
+        ratio: (uncompressed_size > 0).then(|| compressed_size as f64 / uncompressed_size as f64),
+        stored_entries: Some(stored_entries),
+    })
+}
+
+/// Relative paths (forward slashes) and uncompressed sizes of every file in a
+/// pack, read from the archive's central directory or by walking a folder.
+pub fn list_pack_files(path: &Path) -> Result<Vec<(String, u64)>, String> {
+    if path.is_dir() {
+        return Ok(super::skin_pack_4d::collect_relative_files(path)
+            .into_iter()
+            .map(|rel| {
+                let size = fs::metadata(path.join(&rel)).map(|m| m.len()).unwrap_or(0);
+                (rel, size)
+            })
+            .collect());
+    }
+
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Failed to read archive: {}", e))?;
+    let mut files = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        if let Ok(entry) = archive.by_index_raw(i) {
+            if !entry.is_dir() {
+                files.push((entry.name().replace('\\', "/"), entry.size()));
             }
-            writer
-                .write_all(&buffer[..bytes_read])
-                .map_err(|e| format!("Failed to write: {}", e))?;
         }
     }
+    Ok(files)
+}
+
+const README_MAX_BYTES: u64 = 64 * 1024;
+const README_PREFIXES: &[&str] = &["readme", "changelog", "install", "instructions"];
+
+fn is_readme_name(rel: &str) -> bool {
+    let file_name = rel.rsplit('/').next().unwrap_or(rel).to_lowercase();
+    let is_text = file_name.ends_with(".txt") || file_name.ends_with(".md") || !file_name.contains('.');
+    is_text && README_PREFIXES.iter().any(|p| file_name.starts_with(p))
+}
 
-    Ok(output_path.to_string_lossy().to_string())
+/// Text of the shallowest readme, changelog or install notes bundled with a
+/// pack. Files over README_MAX_BYTES are skipped.
+pub fn find_pack_readme(path: &Path) -> Option<String> {
+    let mut candidates: Vec<String> = list_pack_files(path)
+        .ok()?
+        .into_iter()
+        .filter(|(rel, size)| *size > 0 && *size <= README_MAX_BYTES && is_readme_name(rel))
+        .map(|(rel, _)| rel)
+        .collect();
+    candidates.sort_by_key(|rel| rel.matches('/').count());
+    let rel = candidates.into_iter().next()?;
+
+    let bytes = if path.is_dir() {
+        fs::read(path.join(&rel)).ok()?
+    } else {
+        let file = fs::File::open(path).ok()?;
+        let mut archive = ZipArchive::new(file).ok()?;
+        let mut entry = archive.by_name(&rel).ok()?;
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).ok()?;
+        bytes
+    };
+    Some(String::from_utf8_lossy(&bytes).trim_start_matches('\u{feff}').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Writes `entries` into a zip named `name` under a fresh temp folder.
+    fn write_zip(name: &str, entries: &[(&str, &[u8])]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("blocksmith-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let mut writer = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+        for (entry, data) in entries {
+            writer.start_file(*entry, zip::write::SimpleFileOptions::default()).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap();
+        path
+    }
+
+    fn open_zip(path: &Path) -> ZipArchive<fs::File> {
+        ZipArchive::new(fs::File::open(path).unwrap()).unwrap()
+    }
+
+    const MASHUP_MANIFEST: &str = r#"{
+        "format_version": 2,
+        "header": { "name": "Castle World", "uuid": "6f3c1d2e-0000-4000-8000-000000000001", "version": [1, 0, 0] },
+        "modules": [
+            { "type": "world_template", "uuid": "6f3c1d2e-0000-4000-8000-000000000002", "version": [1, 0, 0] },
+            { "type": "skin_pack", "uuid": "6f3c1d2e-0000-4000-8000-000000000003", "version": [1, 0, 0] }
+        ]
+    }"#;
+
+    #[test]
+    fn world_template_with_skin_module_is_mashup() {
+        let json: Value = serde_json::from_str(MASHUP_MANIFEST).unwrap();
+        assert_eq!(determine_pack_type(&json), PackType::MashupPack);
+    }
+
+    #[test]
+    fn plain_world_template_is_not_mashup() {
+        let json = serde_json::json!({
+            "header": { "name": "Castle World" },
+            "modules": [{ "type": "world_template" }]
+        });
+        assert_eq!(determine_pack_type(&json), PackType::WorldTemplate);
+    }
+
+    #[test]
+    fn plainly_named_mctemplate_with_both_modules_is_mashup() {
+        let path = write_zip("Castle World.mctemplate", &[("manifest.json", MASHUP_MANIFEST.as_bytes())]);
+        let manifest = read_archive_manifest(&mut open_zip(&path), "");
+        let (pack_type, uuid, _) = get_pack_info_from_manifest(manifest.as_ref(), "");
+        assert_eq!(pack_type, PackType::MashupPack);
+        assert_eq!(uuid.as_deref(), Some("6f3c1d2e-0000-4000-8000-000000000001"));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    /// A 1x1 PNG of `rgb`, so each icon entry has distinct bytes.
+    fn png(rgb: [u8; 3]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image::RgbImage::from_pixel(1, 1, image::Rgb(rgb))
+            .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn shallowest_icon_wins_regardless_of_entry_order() {
+        let deep = png([255, 0, 0]);
+        let nested = png([0, 255, 0]);
+        let root = png([0, 0, 255]);
+        // Upper-case root name forces the fallback scan over every entry
+        let path = write_zip(
+            "Icons.mcpack",
+            &[
+                ("sub/deeper/pack_icon.png", &deep),
+                ("sub/pack_icon.png", &nested),
+                ("PACK_ICON.PNG", &root),
+            ],
+        );
+        let icons = vec!["pack_icon.png".to_string()];
+        let icon = extract_icon_from_archive(&mut open_zip(&path), "", &icons);
+        assert_eq!(icon, encode_icon_data(&root, "pack_icon.png"));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn icon_name_priority_breaks_depth_ties() {
+        let pack_icon = png([255, 255, 0]);
+        let world_icon = png([0, 255, 255]);
+        let path = write_zip(
+            "Icons.mcpack",
+            &[("b/World_Icon.jpeg", &world_icon), ("a/Pack_Icon.png", &pack_icon)],
+        );
+        let icons = vec!["pack_icon.png".to_string(), "world_icon.jpeg".to_string()];
+        let icon = extract_icon_from_archive(&mut open_zip(&path), "", &icons);
+        assert_eq!(icon, encode_icon_data(&pack_icon, "pack_icon.png"));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
 }
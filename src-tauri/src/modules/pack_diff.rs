@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use zip::ZipArchive;
+
+use super::file_mover::{RECEIPT_FILE_NAME, STAGED_FILE_NAME};
+use super::pack_detector::{extract_uuid, extract_version};
+use super::skin_pack_4d::collect_relative_files;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackDiff {
+    pub path_a: String,
+    pub path_b: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+    pub unchanged_count: usize,
+    pub uuid_a: Option<String>,
+    pub uuid_b: Option<String>,
+    pub version_a: Option<String>,
+    pub version_b: Option<String>,
+    pub uuid_changed: bool,
+    pub version_changed: bool,
+}
+
+struct PackContents {
+    // Relative path (lowercased, forward slashes) -> SHA-256 of the content
+    files: BTreeMap<String, String>,
+    manifest: Option<Value>,
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Folder holding the shallowest manifest.json, so an archive that wraps the
+/// pack in an extra top-level folder still lines up with the extracted pack.
+fn manifest_root(paths: &[String]) -> String {
+    paths
+        .iter()
+        .filter(|p| p.to_lowercase().ends_with("manifest.json"))
+        .min_by_key(|p| p.matches('/').count())
+        .and_then(|p| p.rfind('/').map(|idx| p[..idx + 1].to_string()))
+        .unwrap_or_default()
+}
+
+/// Files Blocksmith writes into installed packs, which aren't part of the pack.
+fn is_blocksmith_file(key: &str) -> bool {
+    key == RECEIPT_FILE_NAME || key == STAGED_FILE_NAME
+}
+
+fn read_folder_contents(root: &Path) -> Result<PackContents, String> {
+    let relative = collect_relative_files(root);
+    let prefix = manifest_root(&relative);
+
+    let mut files = BTreeMap::new();
+    let mut manifest = None;
+    for rel in relative.iter().filter(|r| r.starts_with(&prefix)) {
+        let key = rel[prefix.len()..].to_lowercase();
+        if is_blocksmith_file(&key) {
+            continue;
+        }
+        let bytes = fs::read(root.join(rel)).map_err(|e| format!("Failed to read {}: {}", rel, e))?;
+        if key == "manifest.json" {
+            manifest = serde_json::from_slice(&bytes).ok();
+        }
+        files.insert(key, hash_bytes(&bytes));
+    }
+
+    Ok(PackContents { files, manifest })
+}
+
+fn read_archive_contents(path: &Path) -> Result<PackContents, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    let mut names = Vec::new();
+    for i in 0..archive.len() {
+        if let Ok(entry) = archive.by_index(i) {
+            if !entry.is_dir() {
+                names.push(entry.name().replace('\\', "/"));
+            }
+        }
+    }
+    let prefix = manifest_root(&names);
+
+    let mut files = BTreeMap::new();
+    let mut manifest = None;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().replace('\\', "/");
+        if !name.starts_with(&prefix) {
+            continue;
+        }
+        let key = name[prefix.len()..].to_lowercase();
+        if is_blocksmith_file(&key) {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        if key == "manifest.json" {
+            manifest = serde_json::from_slice(&bytes).ok();
+        }
+        files.insert(key, hash_bytes(&bytes));
+    }
+
+    Ok(PackContents { files, manifest })
+}
+
+fn read_pack_contents(path: &Path) -> Result<PackContents, String> {
+    if path.is_dir() {
+        read_folder_contents(path)
+    } else if path.is_file() {
+        read_archive_contents(path)
+    } else {
+        Err(format!("Path does not exist: {}", path.display()))
+    }
+}
+
+/// Compares two packs, each either an extracted folder or a pack archive.
+pub fn diff_pack_paths(path_a: &Path, path_b: &Path) -> Result<PackDiff, String> {
+    let a = read_pack_contents(path_a)?;
+    let b = read_pack_contents(path_b)?;
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut changed = Vec::new();
+    let mut unchanged_count = 0;
+
+    for (name, hash_a) in &a.files {
+        match b.files.get(name) {
+            Some(hash_b) if hash_b == hash_a => unchanged_count += 1,
+            Some(_) => changed.push(name.clone()),
+            None => removed.push(name.clone()),
+        }
+    }
+    for name in b.files.keys() {
+        if !a.files.contains_key(name) {
+            added.push(name.clone());
+        }
+    }
+
+    let uuid_a = a.manifest.as_ref().and_then(extract_uuid);
+    let uuid_b = b.manifest.as_ref().and_then(extract_uuid);
+    let version_a = a.manifest.as_ref().and_then(extract_version);
+    let version_b = b.manifest.as_ref().and_then(extract_version);
+
+    Ok(PackDiff {
+        path_a: path_a.to_string_lossy().to_string(),
+        path_b: path_b.to_string_lossy().to_string(),
+        added,
+        removed,
+        changed,
+        unchanged_count,
+        uuid_changed: uuid_a != uuid_b,
+        version_changed: version_a != version_b,
+        uuid_a,
+        uuid_b,
+        version_a,
+        version_b,
+    })
+}
@@ -0,0 +1,33 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use super::file_mover::RECEIPT_FILE_NAME;
+use super::skin_pack_4d::collect_relative_files;
+
+/// Content fingerprint of an extracted pack: one hash per (relative path, size)
+/// pair, so re-uploads that only touched a few files still overlap heavily.
+pub fn fingerprint_folder(root: &Path) -> HashSet<u64> {
+    collect_relative_files(root)
+        .into_iter()
+        .filter(|rel| !rel.ends_with(RECEIPT_FILE_NAME))
+        .filter_map(|rel| {
+            let size = fs::metadata(root.join(&rel)).ok()?.len();
+            let mut hasher = DefaultHasher::new();
+            rel.to_lowercase().hash(&mut hasher);
+            size.hash(&mut hasher);
+            Some(hasher.finish())
+        })
+        .collect()
+}
+
+/// Jaccard similarity of two fingerprints, from 0.0 to 1.0.
+pub fn fingerprint_similarity(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::pack_detector::list_pack_files;
+
+/// Top-level pack folders worth counting, as (folder name, category label).
+/// Resource packs keep client entities under "entity", behavior packs under "entities".
+const CONTENT_FOLDERS: &[(&str, &str)] = &[
+    ("entities", "entities"),
+    ("entity", "entities"),
+    ("items", "items"),
+    ("blocks", "blocks"),
+    ("animations", "animations"),
+    ("animation_controllers", "animation_controllers"),
+    ("attachables", "attachables"),
+    ("loot_tables", "loot_tables"),
+    ("recipes", "recipes"),
+    ("spawn_rules", "spawn_rules"),
+    ("trading", "trading"),
+    ("functions", "functions"),
+    ("structures", "structures"),
+    ("scripts", "scripts"),
+    ("models", "models"),
+    ("particles", "particles"),
+    ("render_controllers", "render_controllers"),
+    ("textures", "textures"),
+    ("sounds", "sounds"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentProfile {
+    pub path: String,
+    pub counts: BTreeMap<String, usize>,
+    pub total_files: usize,
+}
+
+/// Counts files under the well-known content folders of every manifest root in
+/// the pack. Archives are profiled from their central directory, without
+/// extracting anything.
+pub fn profile_pack_contents(path: &Path) -> Result<ContentProfile, String> {
+    if !path.exists() {
+        return Err(format!("Path does not exist: {}", path.display()));
+    }
+    let files = list_pack_files(path)?;
+
+    // Packs can be wrapped in a folder, or bundle several packs side by side
+    let roots: Vec<String> = files
+        .iter()
+        .filter_map(|(rel, _)| {
+            let lower = rel.to_lowercase();
+            lower
+                .strip_suffix("manifest.json")
+                .filter(|r| r.is_empty() || r.ends_with('/'))
+                .map(|r| r.to_string())
+        })
+        .collect();
+
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for (rel, _) in &files {
+        let lower = rel.to_lowercase();
+        let Some(root) = roots.iter().filter(|r| lower.starts_with(r.as_str())).max_by_key(|r| r.len()) else {
+            continue;
+        };
+        let inner = &lower[root.len()..];
+        let Some((folder, _)) = inner.split_once('/') else {
+            continue;
+        };
+        if let Some((_, label)) = CONTENT_FOLDERS.iter().find(|(name, _)| *name == folder) {
+            *counts.entry(label.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    Ok(ContentProfile {
+        path: path.to_string_lossy().to_string(),
+        counts,
+        total_files: files.len(),
+    })
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PackType {
@@ -25,10 +26,114 @@ impl std::fmt::Display for PackType {
     }
 }
 
+impl PackType {
+    /// Suffix appended to extracted folder names, e.g. "My Pack (RESOURCE)".
+    pub fn folder_suffix(&self) -> &'static str {
+        match self {
+            PackType::BehaviorPack => " (ADDON)",
+            PackType::ResourcePack => " (RESOURCE)",
+            PackType::SkinPack => " (SKIN)",
+            PackType::SkinPack4D => "",
+            PackType::WorldTemplate => " (TEMPLATE)",
+            PackType::MashupPack => " (MASHUP)",
+            PackType::Unknown => "",
+        }
+    }
+
+    /// Module type a manifest declares for this pack type, e.g. "data" for behavior packs.
+    pub fn manifest_module_type(&self) -> Option<&'static str> {
+        match self {
+            PackType::BehaviorPack => Some("data"),
+            PackType::ResourcePack => Some("resources"),
+            PackType::SkinPack | PackType::SkinPack4D => Some("skin_pack"),
+            PackType::WorldTemplate => Some("world_template"),
+            PackType::MashupPack | PackType::Unknown => None,
+        }
+    }
+
+    pub fn install_rank(&self) -> u8 {
+        match self {
+            PackType::BehaviorPack => 0,
+            PackType::ResourcePack => 1,
+            PackType::WorldTemplate | PackType::MashupPack => 2,
+            PackType::SkinPack | PackType::SkinPack4D => 3,
+            PackType::Unknown => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AttentionReason {
+    Encrypted,
+    MissingManifest,
+    MultipleGeometryFolders,
+    ContainsReadme,
+    TypeMismatch,
+    DependencyMissing,
+    VersionIncompatible,
+    LegacyFormat,
+    MissingTextures,
+    GuessedType,
+    MacJunkFiles,
+}
+
+impl AttentionReason {
+    pub fn message(&self) -> &'static str {
+        match self {
+            AttentionReason::Encrypted => "Password-protected archive — cannot read",
+            AttentionReason::MissingManifest => "No manifest found — cannot determine pack type",
+            AttentionReason::MultipleGeometryFolders => "Multiple geometry folders detected",
+            AttentionReason::ContainsReadme => "Contains instructions/readme",
+            AttentionReason::TypeMismatch => "Pack type does not match its contents",
+            AttentionReason::DependencyMissing => "A required dependency is missing",
+            AttentionReason::VersionIncompatible => "Pack targets an incompatible version",
+            AttentionReason::LegacyFormat => "Legacy format — may not load in current Minecraft",
+            AttentionReason::MissingTextures => "Skin pack references missing textures",
+            AttentionReason::GuessedType => "Pack type guessed from folder names",
+            AttentionReason::MacJunkFiles => "Contains macOS junk files",
+        }
+    }
+
+    pub fn is_4d_special(&self) -> bool {
+        matches!(self, AttentionReason::MultipleGeometryFolders | AttentionReason::ContainsReadme)
+    }
+}
+
+pub fn attention_message_for(reasons: &[AttentionReason]) -> Option<String> {
+    attention_message_with(reasons, |_| None)
+}
+
+/// Like `attention_message_for`, but `detail` can replace a reason's stock
+/// message with a more specific one (e.g. listing the missing files).
+pub fn attention_message_with(
+    reasons: &[AttentionReason],
+    detail: impl Fn(&AttentionReason) -> Option<String>,
+) -> Option<String> {
+    if reasons.is_empty() {
+        return None;
+    }
+
+    let mut messages: Vec<String> = reasons
+        .iter()
+        .map(|r| detail(r).unwrap_or_else(|| r.message().to_string()))
+        .collect();
+    if reasons.iter().any(|r| r.is_4d_special()) {
+        messages.push("May require manual setup".to_string());
+        messages.push("SkinMaster may not work with this pack".to_string());
+    }
+
+    Some(messages.join(". ") + ".")
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackInfo {
     pub path: String,
     pub name: String,
+    /// Archive filename with suffixes cleaned off. Install folders are named
+    /// from this rather than `name`, so packs installed before manifest names
+    /// were shown keep updating in place.
+    #[serde(default)]
+    pub file_name: Option<String>,
     pub pack_type: PackType,
     pub uuid: Option<String>,
     pub version: Option<String>,
@@ -37,15 +142,51 @@ pub struct PackInfo {
     pub subfolder: Option<String>,
     pub folder_size: Option<u64>,
     pub folder_size_formatted: Option<String>,
+    /// Sum of the archive entries' uncompressed sizes, for source packs
+    pub uncompressed_size: Option<u64>,
     pub needs_attention: Option<bool>,
     pub attention_message: Option<String>,
+    #[serde(default)]
+    pub attention_reasons: Vec<AttentionReason>,
     pub is_installed: Option<bool>,
     pub is_update: Option<bool>,
     pub installed_version: Option<String>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    pub format_version: Option<u32>,
+    /// Optional subfolder under the type's destination, e.g. "UI/Menus".
+    pub category: Option<String>,
+    pub linked_to: Option<String>,
+    pub receipt: Option<InstallReceipt>,
+    /// User labels from `Settings::pack_tags`, filled in by UUID
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl PackInfo {
+    /// Name install folders and update matching are based on.
+    pub fn install_base_name(&self) -> &str {
+        self.file_name.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Provenance written into an extracted pack folder as `.blocksmith.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallReceipt {
+    pub source: String,
+    pub source_hash: Option<String>,
+    pub installed_at: String,
+    pub pack_type: PackType,
+    pub uuid: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
+    pub settings_version: Option<u32>,
     pub behavior_pack_path: Option<String>,
     pub resource_pack_path: Option<String>,
     pub skin_pack_path: Option<String>,
@@ -67,11 +208,35 @@ pub struct Settings {
     pub background_style: Option<String>,
     pub background_smoke: Option<u32>,
     pub background_blobs: Option<u32>,
+    pub scan_unknown_extensions: Option<bool>,
+    pub ordered_install: Option<bool>,
+    pub skip_identical: Option<bool>,
+    pub scan_recursive: Option<bool>,
+    pub scan_depth: Option<u32>,
+    pub write_receipt: Option<bool>,
+    pub icon_filenames: Option<Vec<String>>,
+    pub ordered_logs: Option<bool>,
+    pub sort_by: Option<String>,
+    pub sort_desc: Option<bool>,
+    pub template_update_mode: Option<String>,
+    pub install_name_template: Option<String>,
+    pub skinmaster_path: Option<String>,
+    pub keep_old_versions: Option<usize>,
+    pub update_size_threshold: Option<f64>,
+    pub safe_mode: Option<bool>,
+    pub staging_dir: Option<String>,
+    pub verify_after_extract: Option<bool>,
+    pub extract_exclude_globs: Vec<String>,
+    /// User labels keyed by lowercase pack UUID
+    pub pack_tags: HashMap<String, Vec<String>>,
+    /// Folder under the scan location that 4D skin packs extract into
+    pub skin_4d_subfolder_name: Option<String>,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            settings_version: Some(1),
             behavior_pack_path: None,
             resource_pack_path: None,
             skin_pack_path: None,
@@ -93,6 +258,58 @@ impl Default for Settings {
             background_style: Some("embers".to_string()),
             background_smoke: Some(5),
             background_blobs: Some(5),
+            scan_unknown_extensions: Some(false),
+            ordered_install: Some(false),
+            skip_identical: Some(false),
+            scan_recursive: Some(false),
+            scan_depth: Some(3),
+            write_receipt: Some(false),
+            icon_filenames: None,
+            ordered_logs: Some(false),
+            sort_by: Some("name".to_string()),
+            sort_desc: Some(false),
+            template_update_mode: Some("replace".to_string()),
+            install_name_template: None,
+            skinmaster_path: None,
+            keep_old_versions: None,
+            update_size_threshold: Some(1.1),
+            safe_mode: Some(false),
+            staging_dir: None,
+            verify_after_extract: Some(true),
+            extract_exclude_globs: Vec::new(),
+            pack_tags: HashMap::new(),
+            skin_4d_subfolder_name: Some(DEFAULT_SKIN_4D_SUBFOLDER.to_string()),
+        }
+    }
+}
+
+/// Used when Settings.skin_4d_subfolder_name is unset or not a plain folder name.
+pub const DEFAULT_SKIN_4D_SUBFOLDER: &str = "4D Skin Packs";
+
+/// Icon filenames checked in priority order when Settings.icon_filenames is unset.
+pub const DEFAULT_ICON_FILENAMES: &[&str] = &[
+    "pack_icon.png",
+    "Pack_Icon.png",
+    "world_icon.jpeg",
+    "world_icon.jpg",
+    "pack_icon.webp",
+    "pack_icon.tga",
+    "icon.png",
+];
+
+impl Settings {
+    pub fn icon_names(&self) -> Vec<String> {
+        match &self.icon_filenames {
+            Some(names) if !names.is_empty() => names.clone(),
+            _ => DEFAULT_ICON_FILENAMES.iter().map(|n| n.to_string()).collect(),
+        }
+    }
+
+    /// Name of the 4D skin pack folder created next to the scanned packs.
+    pub fn skin_4d_subfolder(&self) -> &str {
+        match self.skin_4d_subfolder_name.as_deref().map(str::trim) {
+            Some(name) if !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\', ':']) => name,
+            _ => DEFAULT_SKIN_4D_SUBFOLDER,
         }
     }
 }
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::pack_detector::{parse_manifest_json, read_folder_display_name};
+use super::skin_pack_4d::{collect_relative_files, find_skins_json};
+
+/// Geometry ids used by the standard Steve and Alex models; anything else is a 4D skin.
+const STANDARD_GEOMETRIES: &[&str] = &["geometry.humanoid.custom", "geometry.humanoid.customSlim"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkinEntry {
+    pub pack_name: String,
+    pub pack_path: String,
+    pub skin_name: String,
+    pub geometry: Option<String>,
+    pub is_4d: bool,
+    pub texture: Option<String>,
+    /// Geometry file, relative to the pack, that defines `geometry`
+    #[serde(default)]
+    pub geometry_file: Option<String>,
+}
+
+/// Looks up `skin.<pack>.<skin>` in the pack's en_US.lang, which is where
+/// skin packs keep the names shown in the in-game picker.
+fn localized_skin_name(lang: Option<&str>, pack_key: Option<&str>, skin_key: &str) -> Option<String> {
+    let lang = lang?;
+    let key = format!("skin.{}.{}", pack_key?, skin_key);
+    lang.lines().find_map(|line| {
+        let (k, v) = line.split_once('=')?;
+        if k.trim() != key {
+            return None;
+        }
+        let v = v.split('#').next().unwrap_or(v).trim();
+        (!v.is_empty()).then(|| v.to_string())
+    })
+}
+
+/// Lists every skin in an installed skin pack folder. Returns None when the
+/// pack has no readable skins.json.
+pub fn read_pack_skins(folder: &Path) -> Option<Vec<SkinEntry>> {
+    read_skins_json(folder, &collect_relative_files(folder)).ok()
+}
+
+/// Lists every skin in a 4D skin pack along with the geometry file that
+/// defines its model, wherever in the pack skins.json lives.
+pub fn list_4d_skins(folder: &Path) -> Result<Vec<SkinEntry>, String> {
+    let files = collect_relative_files(folder);
+    let mut entries = read_skins_json(folder, &files)?;
+    let geometry_files = geometry_identifiers(folder, &files);
+    for entry in &mut entries {
+        entry.geometry_file = entry
+            .geometry
+            .as_ref()
+            .and_then(|id| geometry_files.get(id))
+            .cloned();
+    }
+    Ok(entries)
+}
+
+/// Maps each geometry identifier declared in the pack's geometry files to the
+/// file declaring it. Unreadable geometry files are skipped.
+fn geometry_identifiers(folder: &Path, files: &[String]) -> HashMap<String, String> {
+    let mut identifiers = HashMap::new();
+    for file in files {
+        let lower = file.to_lowercase();
+        if !lower.contains("geometry") || !lower.ends_with(".json") {
+            continue;
+        }
+        let Some(json) = fs::read_to_string(folder.join(file))
+            .ok()
+            .and_then(|content| parse_manifest_json(&content))
+        else {
+            continue;
+        };
+
+        // 1.12+ files list models under minecraft:geometry; older ones key
+        // each model by id, optionally suffixed with ":<parent>"
+        let modern = json
+            .get("minecraft:geometry")
+            .and_then(|g| g.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|model| model.pointer("/description/identifier")?.as_str());
+        let legacy = json
+            .as_object()
+            .into_iter()
+            .flat_map(|obj| obj.keys())
+            .filter(|key| key.starts_with("geometry."))
+            .map(|key| key.split(':').next().unwrap_or(key));
+        for id in modern.chain(legacy) {
+            identifiers.entry(id.to_string()).or_insert_with(|| file.clone());
+        }
+    }
+    identifiers
+}
+
+fn read_skins_json(folder: &Path, files: &[String]) -> Result<Vec<SkinEntry>, String> {
+    let skins_path = find_skins_json(folder, files).ok_or_else(|| "No skins.json found".to_string())?;
+    let content = fs::read_to_string(&skins_path)
+        .map_err(|e| format!("Failed to read skins.json: {}", e))?;
+    let json = parse_manifest_json(&content).ok_or_else(|| {
+        match serde_json::from_str::<Value>(&content) {
+            Err(e) => format!("skins.json is malformed (line {}, column {}): {}", e.line(), e.column(), e),
+            Ok(_) => "skins.json is malformed".to_string(),
+        }
+    })?;
+    let skins = json
+        .get("skins")
+        .and_then(|s| s.as_array())
+        .ok_or_else(|| "skins.json has no \"skins\" list".to_string())?;
+
+    let pack_dir = skins_path.parent().unwrap_or(folder);
+    let lang = fs::read_to_string(pack_dir.join("texts").join("en_US.lang")).ok();
+    let pack_key = json.get("localization_name").and_then(|n| n.as_str());
+    let pack_name = read_folder_display_name(pack_dir)
+        .or_else(|| pack_key.map(|k| k.to_string()))
+        .unwrap_or_else(|| folder.file_name().unwrap_or_default().to_string_lossy().to_string());
+    let pack_path = folder.to_string_lossy().to_string();
+
+    let entries = skins
+        .iter()
+        .map(|skin: &Value| {
+            let skin_key = skin.get("localization_name").and_then(|n| n.as_str()).unwrap_or_default();
+            let skin_name = localized_skin_name(lang.as_deref(), pack_key, skin_key)
+                .unwrap_or_else(|| skin_key.to_string());
+            let geometry = skin.get("geometry").and_then(|g| g.as_str()).map(|g| g.to_string());
+            let is_4d = geometry
+                .as_deref()
+                .is_some_and(|g| !STANDARD_GEOMETRIES.contains(&g));
+
+            SkinEntry {
+                pack_name: pack_name.clone(),
+                pack_path: pack_path.clone(),
+                skin_name,
+                geometry,
+                is_4d,
+                texture: skin.get("texture").and_then(|t| t.as_str()).map(|t| t.to_string()),
+                geometry_file: None,
+            }
+        })
+        .collect();
+
+    Ok(entries)
+}
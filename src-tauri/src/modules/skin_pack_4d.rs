@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Validation4D {
+    pub path: String,
+    pub is_valid: bool,
+    pub has_skins_json: bool,
+    pub skins_json_subfolder: Option<String>,
+    pub geometry_files: Vec<String>,
+    pub geometry_folders: Vec<String>,
+    pub missing_textures: Vec<String>,
+    pub has_readme: bool,
+    pub needs_attention: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Lists every file under `root` as a forward-slash relative path.
+pub fn collect_relative_files(root: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(current) = stack.pop() {
+        if let Ok(entries) = fs::read_dir(&current) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    stack.push(path);
+                } else if let Ok(relative) = path.strip_prefix(root) {
+                    files.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+/// Finds skins.json at the root of the pack, or in the shallowest subfolder that has one.
+pub fn find_skins_json(root: &Path, files: &[String]) -> Option<PathBuf> {
+    files
+        .iter()
+        .filter(|f| f.to_lowercase().ends_with("skins.json"))
+        .min_by_key(|f| f.matches('/').count())
+        .map(|f| root.join(f))
+}
+
+pub fn validate_4d_folder(path: &Path) -> Validation4D {
+    let files = collect_relative_files(path);
+
+    let mut has_readme = false;
+    let mut geometry_files = Vec::new();
+    let mut geometry_folders: HashSet<String> = HashSet::new();
+
+    for file in &files {
+        let name = file.to_lowercase();
+
+        if (name.contains("readme") || name.contains("instructions") || name.contains("install"))
+            && (name.ends_with(".txt") || name.ends_with(".md"))
+        {
+            has_readme = true;
+        }
+
+        if name.contains("geometry") && name.ends_with(".json") {
+            geometry_files.push(file.clone());
+        }
+
+        if name.contains("geometry") && name.contains('/') {
+            if let Some(folder) = name.split('/').next() {
+                geometry_folders.insert(folder.to_string());
+            }
+        }
+    }
+
+    let skins_json_path = find_skins_json(path, &files);
+    let skins_json_subfolder = skins_json_path
+        .as_ref()
+        .and_then(|p| p.parent())
+        .and_then(|p| p.strip_prefix(path).ok())
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .filter(|s| !s.is_empty());
+
+    let mut missing_textures = Vec::new();
+    let mut skins_json_error: Option<String> = None;
+
+    if let Some(ref skins_path) = skins_json_path {
+        let texture_base = skins_path.parent().unwrap_or(path);
+        match fs::read_to_string(skins_path)
+            .map_err(|e| e.to_string())
+            .and_then(|c| serde_json::from_str::<Value>(&c).map_err(|e| e.to_string()))
+        {
+            Ok(json) => {
+                let lower_files: HashSet<String> = files.iter().map(|f| f.to_lowercase()).collect();
+                for texture in skins_json_textures(&json) {
+                    let exists = texture_base.join(&texture).exists()
+                        || lower_files.contains(&texture.to_lowercase());
+                    if !exists && !missing_textures.contains(&texture) {
+                        missing_textures.push(texture);
+                    }
+                }
+            }
+            Err(e) => skins_json_error = Some(format!("skins.json could not be parsed: {}", e)),
+        }
+    }
+
+    let mut geometry_folders: Vec<String> = geometry_folders.into_iter().collect();
+    geometry_folders.sort();
+
+    let has_skins_json = skins_json_path.is_some();
+    let has_multiple_geometry_folders = geometry_folders.len() > 1;
+
+    let mut warnings = Vec::new();
+    if !has_skins_json {
+        warnings.push("No skins.json found".to_string());
+    }
+    if let Some(e) = skins_json_error {
+        warnings.push(e);
+    }
+    if geometry_files.is_empty() {
+        warnings.push("No geometry files found".to_string());
+    }
+    if !missing_textures.is_empty() {
+        warnings.push(format!("Missing textures: {}", missing_textures.join(", ")));
+    }
+    if has_readme {
+        warnings.push("Contains instructions/readme".to_string());
+    }
+    if has_multiple_geometry_folders {
+        warnings.push("Multiple geometry folders detected".to_string());
+    }
+
+    let needs_attention = !warnings.is_empty();
+    if needs_attention {
+        warnings.push("May require manual setup".to_string());
+        warnings.push("SkinMaster may not work with this pack".to_string());
+    }
+
+    let is_valid = has_skins_json && !geometry_files.is_empty() && missing_textures.is_empty();
+
+    Validation4D {
+        path: path.to_string_lossy().to_string(),
+        is_valid,
+        has_skins_json,
+        skins_json_subfolder,
+        geometry_files,
+        geometry_folders,
+        missing_textures,
+        has_readme,
+        needs_attention,
+        warnings,
+    }
+}
+
+pub fn skins_json_textures(json: &Value) -> Vec<String> {
+    json.get("skins")
+        .and_then(|s| s.as_array())
+        .map(|skins| {
+            skins
+                .iter()
+                .flat_map(|skin| ["texture", "cape"].map(|key| skin.get(key).and_then(|t| t.as_str())))
+                .flatten()
+                .map(|t| t.trim_start_matches("./").to_string())
+                .fold(Vec::new(), |mut textures, t| {
+                    if !textures.contains(&t) {
+                        textures.push(t);
+                    }
+                    textures
+                })
+        })
+        .unwrap_or_default()
+}
@@ -1,7 +1,7 @@
 import { useState, useEffect, useCallback, useRef, useMemo } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import { PackInfo, Settings as SettingsType, LogEntry, ProgressEvent, MoveOperation, getPackKey, PackType, AppNotification } from './types';
+import { PackInfo, Settings as SettingsType, LogEntry, ProgressEvent, MoveOperation, getPackKey, PackType, AppNotification, SourceDeletionReport } from './types';
 import { AnimatedLogViewer } from './components/AnimatedLogViewer';
 import { PackList } from './components/PackList';
 import { Settings, SettingsButton } from './components/Settings';
@@ -364,8 +364,32 @@ function App() {
         const path = fourDSkinPacks[0].destination.replace(/ \(4D SKIN\)$/, '');
         navigator.clipboard.writeText(path).catch(() => {});
       }
+
+      const sources = Array.from(new Set(ops.filter((r) => r.success && !r.reverted).map((r) => r.source)));
+      if (settings.delete_source && !settings.dry_run && sources.length > 0) {
+        setConfirmState({
+          title: `Delete ${sources.length} Source File${sources.length > 1 ? 's' : ''}`,
+          message: `The packs were installed. Delete ${sources.length > 1 ? 'their source files' : 'the source file'} from disk? This cannot be undone.`,
+          detail: sources.join('\n'),
+          onConfirm: async () => {
+            setConfirmState(null);
+            try {
+              const report = await invoke<SourceDeletionReport>('delete_processed_sources', { operations: ops });
+              const deleted = new Set(report.deleted);
+              setPacks((prev) => prev.filter((p) => !deleted.has(p.path)));
+              if (report.errors.length > 0) {
+                addNotification('error', 'Some Deletions Failed', report.errors.join('\n'));
+              } else {
+                addNotification('success', 'Files Deleted', `${deleted.size} source file${deleted.size > 1 ? 's' : ''} deleted from disk.`);
+              }
+            } catch (error) {
+              addNotification('error', 'Delete Failed', `Could not delete source files: ${error}`);
+            }
+          },
+        });
+      }
     }
-  }, []);
+  }, [settings.delete_source, settings.dry_run, addNotification]);
 
   const handleSettingsChange = useCallback((newSettings: SettingsType) => {
     setSettings(newSettings);
@@ -854,7 +878,7 @@ function App() {
                         )}
                       </div>
                       {result.success ? (
-                        <div className="result-dest">{result.destination}</div>
+                        <div className="result-dest">{result.note ? `${result.note} — ${result.destination}` : result.destination}</div>
                       ) : (
                         <div className="result-error">{result.error}</div>
                       )}
@@ -201,6 +201,12 @@ const PackGroupItem = memo(function PackGroupItem({
           <div className="pack-card-path" title={group.mainPack.path}>
             {group.mainPack.path}
           </div>
+          {group.mainPack.receipt && (
+            <div className="pack-card-path" title={group.mainPack.receipt.source}>
+              Installed from {getFolderName(group.mainPack.receipt.source)} on{' '}
+              {new Date(group.mainPack.receipt.installed_at).toLocaleDateString()}
+            </div>
+          )}
         </div>
         <button
           className="btn btn-icon btn-delete"
@@ -1,7 +1,7 @@
 import { useState, useEffect, useCallback, useMemo } from 'react';
 import { PackInfo, PackTypeLabels, PackTypeColors, getPackKey } from '../types';
 import { getFolderName, getBestDisplayName, getBaseNameForGrouping, getIconForPackType } from '../utils/packUtils';
-import { Folder, Trash2, Box, Copy, FileText, Hash, ChevronDown, Check, Minus, RefreshCw, CheckCircle } from 'lucide-react';
+import { Folder, Trash2, Box, Copy, FileText, Hash, ChevronDown, Check, Minus, RefreshCw, CheckCircle, Link } from 'lucide-react';
 
 interface PackListProps {
   packs: PackInfo[];
@@ -74,6 +74,16 @@ function InstallStatusBadge({ pack }: { pack: PackInfo }) {
 }
 
 
+function LinkedPackBadge({ pack, packs }: { pack: PackInfo; packs: PackInfo[] }) {
+  if (!pack.linked_to) return null;
+  const base = packs.find((p) => p.uuid?.toLowerCase() === pack.linked_to);
+  return (
+    <span className="pack-status-badge linked" title={`Depends on ${base ? getBestDisplayName(base) : pack.linked_to}`}>
+      <Link size={10} />
+      Addon
+    </span>
+  );
+}
 
 function CustomCheckbox({ checked, indeterminate, onChange }: { checked: boolean; indeterminate?: boolean; onChange: () => void }) {
   return (
@@ -316,6 +326,7 @@ export function PackList({
                   <div className="pack-name">
                     {group.displayName}
                     <InstallStatusBadge pack={group.mainPack} />
+                    <LinkedPackBadge pack={group.mainPack} packs={packs} />
                     {hasChildren && (
                       <span className="expand-indicator">
                         <ChevronDown 
@@ -1,7 +1,7 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
-import { PackInfo, Settings, MoveOperation, ProgressEvent, getPackKey } from '../types';
+import { PackInfo, Settings, MoveOperation, ProgressEvent, DestinationStatus, getPackKey } from '../types';
 import { Scan, Package, Undo2, Loader2 } from 'lucide-react';
 
 function formatTime(seconds: number): string {
@@ -96,6 +96,22 @@ export function ScanControls({
   const handleProcess = async () => {
     if (selectedPacks.size === 0) return;
 
+    if (!settings.dry_run) {
+      try {
+        const statuses = await invoke<DestinationStatus[]>('precheck_destinations');
+        const blocked = statuses.filter((s) => s.path && !s.writable);
+        if (blocked.length > 0) {
+          onError?.(
+            'Destination not writable',
+            blocked.map((s) => `${s.label}: ${s.error ?? 'not writable'}`).join('\n'),
+          );
+          return;
+        }
+      } catch (error) {
+        console.error('Destination check failed:', error);
+      }
+    }
+
     onMoveStart();
     try {
       const selectedPacksList = packs.filter((p) => selectedPacks.has(getPackKey(p)));
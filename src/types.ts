@@ -1,8 +1,22 @@
 export type PackType = 'BehaviorPack' | 'ResourcePack' | 'SkinPack' | 'SkinPack4D' | 'WorldTemplate' | 'MashupPack' | 'Unknown';
 
+export type AttentionReason =
+  | 'Encrypted'
+  | 'MissingManifest'
+  | 'MultipleGeometryFolders'
+  | 'ContainsReadme'
+  | 'TypeMismatch'
+  | 'DependencyMissing'
+  | 'VersionIncompatible'
+  | 'LegacyFormat'
+  | 'MissingTextures'
+  | 'GuessedType'
+  | 'MacJunkFiles';
+
 export interface PackInfo {
   path: string;
   name: string;
+  file_name?: string;
   pack_type: PackType;
   uuid?: string;
   version?: string;
@@ -11,16 +25,35 @@ export interface PackInfo {
   subfolder?: string;
   folder_size?: number;
   folder_size_formatted?: string;
+  uncompressed_size?: number;
   needs_attention?: boolean;
   attention_message?: string;
+  attention_reasons?: AttentionReason[];
   is_installed?: boolean;
   is_update?: boolean;
   installed_version?: string;
+  dependencies?: string[];
+  description?: string;
+  authors?: string[];
+  format_version?: number;
+  category?: string;
+  linked_to?: string;
+  receipt?: InstallReceipt;
+  tags?: string[];
+}
+
+export interface InstallReceipt {
+  source: string;
+  source_hash?: string;
+  installed_at: string;
+  pack_type: PackType;
+  uuid?: string;
 }
 
 export type BackgroundStyle = 'embers' | 'matrix' | 'mc-terrain' | 'night-sky' | 'none';
 
 export interface Settings {
+  settings_version?: number;
   behavior_pack_path?: string;
   resource_pack_path?: string;
   skin_pack_path?: string;
@@ -42,8 +75,31 @@ export interface Settings {
   background_style?: BackgroundStyle;
   background_smoke?: number;
   background_blobs?: number;
+  scan_unknown_extensions?: boolean;
+  ordered_install?: boolean;
+  skip_identical?: boolean;
+  scan_recursive?: boolean;
+  scan_depth?: number;
+  write_receipt?: boolean;
+  icon_filenames?: string[];
+  ordered_logs?: boolean;
+  sort_by?: PackSortKey;
+  sort_desc?: boolean;
+  template_update_mode?: 'replace' | 'preserve-saves';
+  install_name_template?: string;
+  skinmaster_path?: string;
+  keep_old_versions?: number;
+  update_size_threshold?: number;
+  safe_mode?: boolean;
+  staging_dir?: string;
+  verify_after_extract?: boolean;
+  extract_exclude_globs?: string[];
+  pack_tags?: Record<string, string[]>;
+  skin_4d_subfolder_name?: string;
 }
 
+export type PackSortKey = 'name' | 'size' | 'type' | 'date';
+
 export type ThemeName = 'darkred' | 'minecraft';
 
 export interface AppNotification {
@@ -64,19 +120,33 @@ export interface MoveOperation {
   is_template_update?: boolean;
   skin_pack_4d_path?: string;
   deleted_old_path?: string;
+  archived_old_path?: string;
+  uuid?: string;
+  source_hash?: string;
+  note?: string;
+  timestamp?: string;
+  reverted?: boolean;
+  duration_ms?: number;
+  extracted_bytes?: number;
+  staged?: boolean;
 }
 
 export interface LogEntry {
   timestamp: string;
   level: string;
   message: string;
+  pack?: string;
+  pack_index?: number;
 }
 
+export type ProgressPhase = 'enumerating' | 'scanning' | 'sizing' | 'statusing' | 'loading-installed' | 'loading-icons' | 'importing' | 'repackaging' | 'done';
+
 export interface ProgressEvent {
   current: number;
   total: number;
   message: string;
   estimated_seconds?: number;
+  phase?: ProgressPhase;
 }
 
 export interface PremiumCachePack {
@@ -99,6 +169,263 @@ export interface PackStats {
   total_size_formatted: string;
 }
 
+export interface Validation4D {
+  path: string;
+  is_valid: boolean;
+  has_skins_json: boolean;
+  skins_json_subfolder?: string;
+  geometry_files: string[];
+  geometry_folders: string[];
+  missing_textures: string[];
+  has_readme: boolean;
+  needs_attention: boolean;
+  warnings: string[];
+}
+
+export interface SkinEntry {
+  pack_name: string;
+  pack_path: string;
+  skin_name: string;
+  geometry?: string;
+  is_4d: boolean;
+  texture?: string;
+  geometry_file?: string;
+}
+
+export interface AttentionCategory {
+  kind: string;
+  count: number;
+  pack_names: string[];
+}
+
+export interface AttentionSummary {
+  total: number;
+  categories: AttentionCategory[];
+}
+
+export interface DestinationStatus {
+  label: string;
+  path?: string;
+  exists: boolean;
+  writable: boolean;
+  free_space?: number;
+  free_space_formatted?: string;
+  error?: string;
+}
+
+export interface ActivePack {
+  pack_id: string;
+  version?: string;
+  pack_type: PackType;
+  installed: boolean;
+  installed_name?: string;
+}
+
+export interface WorldActivePacks {
+  world_path: string;
+  active: ActivePack[];
+  inactive_installed: string[];
+}
+
+export interface PackDiff {
+  path_a: string;
+  path_b: string;
+  added: string[];
+  removed: string[];
+  changed: string[];
+  unchanged_count: number;
+  uuid_a?: string;
+  uuid_b?: string;
+  version_a?: string;
+  version_b?: string;
+  uuid_changed: boolean;
+  version_changed: boolean;
+}
+
+export interface PackRename {
+  old_path: string;
+  new_path: string;
+  applied: boolean;
+  error?: string;
+}
+
+export interface ArchiveProblem {
+  entry: string;
+  error: string;
+}
+
+export interface ArchiveHealth {
+  path: string;
+  total_entries: number;
+  good_entries: number;
+  problems: ArchiveProblem[];
+  is_healthy: boolean;
+}
+
+export interface NearDuplicateMember {
+  path: string;
+  name: string;
+  uuid?: string;
+  version?: string;
+}
+
+export interface NearDuplicateGroup {
+  pack_type: PackType;
+  similarity: number;
+  packs: NearDuplicateMember[];
+}
+
+export interface PackNameValidation {
+  original: string;
+  sanitized: string;
+  changed: boolean;
+  issues: string[];
+}
+
+export interface CollectionInstall {
+  operations: MoveOperation[];
+  missing: string[];
+}
+
+export interface ContentProfile {
+  path: string;
+  counts: Record<string, number>;
+  total_files: number;
+}
+
+export interface PackDetails {
+  path: string;
+  exists: boolean;
+  size: number;
+  size_formatted: string;
+  icon?: string;
+  uuid?: string;
+  name?: string;
+  version?: string;
+}
+
+export type DriveKind = 'Fixed' | 'Removable' | 'Network' | 'Unknown';
+
+export interface PathClassification {
+  label: string;
+  path: string;
+  kind: DriveKind;
+}
+
+export interface SourceDeletionReport {
+  deleted: string[];
+  errors: string[];
+}
+
+export interface PathRemap {
+  settings: Settings;
+  missing: string[];
+}
+
+export interface AddonPair {
+  name: string;
+  behavior_pack?: NearDuplicateMember;
+  resource_pack?: NearDuplicateMember;
+  matched_by?: 'dependency' | 'name';
+  missing_dependencies: string[];
+}
+
+export interface PackPlan {
+  path: string;
+  name: string;
+  pack_type: PackType;
+  destination?: string;
+  uncompressed_size: number;
+  is_update: boolean;
+  net_delta: number;
+}
+
+export interface DrivePlan {
+  drive: string;
+  destinations: string[];
+  incoming_size: number;
+  incoming_size_formatted: string;
+  net_size: number;
+  free_space?: number;
+  free_space_formatted?: string;
+  will_fit: boolean;
+}
+
+export interface ImportPlan {
+  drives: DrivePlan[];
+  packs: PackPlan[];
+  will_fit: boolean;
+}
+
+export interface Diagnostics {
+  app_version: string;
+  os: string;
+  arch: string;
+  config_dir: string | null;
+  debug_mode: boolean;
+  safe_mode: boolean;
+  minecraft_running: boolean;
+  destinations: DestinationStatus[];
+  installed_packs: PackStats[];
+}
+
+export interface InstalledStatus {
+  status: 'installed' | 'not_installed' | 'update_available';
+  installed_version: string | null;
+  installed_path: string | null;
+}
+
+export interface Extracted4DPack {
+  name: string;
+  path: string;
+  size: number;
+  size_formatted: string;
+  source: string | null;
+}
+
+export interface WatchedFolder {
+  watcher: string;
+  path: string;
+}
+
+export interface WatcherStatus {
+  active: boolean;
+  watched: WatchedFolder[];
+  events_emitted: number;
+}
+
+export interface JsonError {
+  file: string;
+  message: string;
+  line: number;
+  column: number;
+  lenient_ok: boolean;
+}
+
+export interface CompressionInfo {
+  path: string;
+  total_entries: number;
+  uncompressed_size: number;
+  compressed_size: number | null;
+  ratio: number | null;
+  stored_entries: number | null;
+}
+
+export interface DeleteFolderPreview {
+  label: string;
+  path: string;
+  pack_count: number;
+  size: number;
+  size_formatted: string;
+}
+
+export interface DeleteAllPreview {
+  folders: DeleteFolderPreview[];
+  total_packs: number;
+  total_size: number;
+  total_size_formatted: string;
+}
+
 export function getPackKey(pack: PackInfo): string {
   return `${pack.path}::${pack.subfolder || ''}`;
 }